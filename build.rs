@@ -1,26 +1,119 @@
+use std::collections::{BTreeMap, HashSet};
 use std::env;
 use std::fs;
 use std::path::Path;
 
+const MIN_WORD_LEN: usize = 4;
+const MAX_WORD_LEN: usize = 11;
+
+/// Words the rules in [`is_inflected`] take for another form of a word, but which aren't.
+const NOT_INFLECTED: &[&str] = &["feed", "fling", "seed", "thing", "tweed", "weed"];
+
+/// Whether `word` is just another form of a word in `dictionary`: a plural, a verb ending in -s,
+/// -ed or -ing. These are accepted as guesses but make poor answers.
+fn is_inflected(word: &str, dictionary: &HashSet<&str>) -> bool {
+    if NOT_INFLECTED.contains(&word) {
+        return false;
+    }
+    // Stems of a single letter are never what's meant, as in "king" or "reed"
+    let has_stem = |suffix: &str, replacement: &str| {
+        word.strip_suffix(suffix).is_some_and(|stem| {
+            stem.len() > 1 && dictionary.contains(&*format!("{}{}", stem, replacement))
+        })
+    };
+    // A stem with its last consonant doubled, as in "stopped" or "running"
+    let has_doubled_stem = |suffix: &str| {
+        word.strip_suffix(suffix).is_some_and(|stem| {
+            let bytes = stem.as_bytes();
+            bytes.len() > 2
+                && bytes[bytes.len() - 1] == bytes[bytes.len() - 2]
+                && dictionary.contains(&stem[..stem.len() - 1])
+        })
+    };
+
+    (has_stem("s", "") && !word.ends_with("ss"))
+        || has_stem("es", "")
+        || has_stem("ies", "y")
+        || has_stem("ed", "")
+        // Only verbs ending in e take a bare -d, so "heard" and "board" are words of their own
+        || (has_stem("d", "") && word.ends_with("ed"))
+        || has_stem("ied", "y")
+        || has_doubled_stem("ed")
+        || has_stem("ing", "")
+        || has_stem("ing", "e")
+        || has_doubled_stem("ing")
+}
+
+fn write_lists(out: &mut String, kind: &str, lists: &BTreeMap<usize, Vec<&str>>) {
+    for (len, words) in lists {
+        *out += &format!(
+            "pub static {}_LIST_{}: [&str; {}] = [\n{}\n];\n",
+            kind,
+            len,
+            words.len(),
+            words
+                .iter()
+                .map(|x| format!("    \"{}\",", x.to_uppercase()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+    }
+    *out += &format!(
+        "pub static {}_LISTS: [&[&str]; {}] = [{}];\n",
+        kind,
+        lists.len(),
+        lists.keys().map(|len| format!("&{}_LIST_{}", kind, len)).collect::<Vec<_>>().join(", ")
+    );
+    *out += &format!("pub static {0}_LIST: &[&str] = &{0}_LIST_5;\n", kind);
+}
+
 fn main() {
+    // words5.txt is the five-letter list the game has always used. Every other length is taken
+    // from the full dictionary, keeping only plain lowercase words (no proper nouns, possessives
+    // or accented letters).
     let word_list_str = fs::read_to_string("words5.txt").unwrap().trim().to_string();
+    let dictionary = fs::read("words.txt").unwrap();
+    let dictionary = String::from_utf8_lossy(&dictionary);
+    let dictionary: HashSet<&str> = dictionary
+        .lines()
+        .map(str::trim)
+        .filter(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase()))
+        .collect();
+
+    // Every word in the lists can be guessed; only the ones that aren't inflections of another
+    // word are picked as answers
+    let mut guess_lists: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    guess_lists.insert(5, word_list_str.lines().collect());
+    for &word in &dictionary {
+        let len = word.len();
+        if (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&len) && len != 5 {
+            guess_lists.entry(len).or_default().push(word);
+        }
+    }
+    let mut answer_lists: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for (len, words) in &mut guess_lists {
+        // Sorted, so that lookups can use a binary search
+        words.sort_unstable();
+        words.dedup();
+        answer_lists.insert(
+            *len,
+            words.iter().copied().filter(|word| !is_inflected(word, &dictionary)).collect(),
+        );
+    }
+
+    let mut out = String::new();
+    write_lists(&mut out, "ANSWER", &answer_lists);
+    write_lists(&mut out, "GUESS", &guess_lists);
+    out += &format!(
+        "pub const MIN_WORD_LEN: usize = {};\npub const MAX_WORD_LEN: usize = {};\n",
+        MIN_WORD_LEN, MAX_WORD_LEN
+    );
 
     let out_dir = env::var_os("OUT_DIR").unwrap();
     let dest_path = Path::new(&out_dir).join("word_list.rs");
-    #[rustfmt::skip] // rustfmt wants to put the final .unwrap() on a new line
-    fs::write(
-        &dest_path,
-        format!(
-            "pub static WORD_LIST: [&str; {}] = [\n{}\n];",
-            word_list_str.lines().count(),
-            word_list_str
-                .lines()
-                .map(|x| format!("    \"{}\",", x.to_uppercase()))
-                .collect::<Vec<String>>()
-                .join("\n")
-        ),
-    ).unwrap();
+    fs::write(&dest_path, out).unwrap();
 
     println!("cargo:rerun-if-changed=build.rs");
     println!("cargo:rerun-if-changed=words5.txt");
+    println!("cargo:rerun-if-changed=words.txt");
 }
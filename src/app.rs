@@ -1,63 +1,72 @@
-use crate::{draw, utils};
+use crate::challenge::{self, Challenge};
+use crate::engine::{
+    Game, GuessError, MultiGame, Rules, SavedGame, BOARD_COUNTS, DEFAULT_MAX_GUESSES, MAX_GUESSES,
+    MIN_GUESSES,
+};
+use crate::keyboard::{KeyboardLayout, SpecialKeys};
+use crate::language::{self, Language};
+use crate::solver::Solver;
+use crate::stats::Stats;
+use crate::theme::Theme;
+use crate::timer::{SavedTimer, Timer, Timing, DEFAULT_TIME_LIMIT, MAX_TIME_LIMIT, MIN_TIME_LIMIT};
+use crate::words::WordList;
+use crate::{daily, draw, engine, qr, utils};
 use eframe::egui::{Color32, Key, Layout, RichText};
 use eframe::{egui, epi};
-use std::collections::HashMap;
+use std::sync::Arc;
 
-const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
-const KBD_ROW1: &str = "QWERTYUIOP";
-const KBD_ROW2: &str = "ASDFGHJKL";
-const KBD_ROW3: &str = "ZXCVBNM";
+pub(crate) use crate::engine::{CellState, GameState};
 
-#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
-//#[cfg_attr(feature = "persistence", serde(default))]
-#[derive(Debug, Clone)]
-pub(crate) enum CellState {
-    Empty,
-    Gray,
-    Yellow,
-    Green,
+/// How cells are drawn, worked out each frame from the settings and the dark/light switch.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Palette {
+    pub theme: Theme,
+    pub dark_mode: bool,
+    /// Mark scored cells with a shape as well as a color.
+    pub symbols: bool,
 }
 
 impl CellState {
-    pub(crate) fn get_color(&self, keyboard: bool) -> Color32 {
-        match self {
-            Self::Empty => {
-                if keyboard {
-                    Color32::GRAY
-                } else {
-                    Color32::BLACK
-                }
-            }
-            Self::Gray => Color32::DARK_GRAY,
-            Self::Yellow => Color32::from_rgb(181, 159, 59),
-            Self::Green => Color32::from_rgb(83, 141, 78),
+    pub(crate) fn get_color(&self, keyboard: bool, palette: Palette) -> Color32 {
+        match palette.theme.rgb(*self, palette.dark_mode) {
+            Some([r, g, b]) => Color32::from_rgb(r, g, b),
+            None => match (keyboard, palette.dark_mode) {
+                (true, true) => Color32::GRAY,
+                (false, true) => Color32::BLACK,
+                (true, false) => Color32::from_gray(211),
+                (false, false) => Color32::from_gray(235),
+            },
         }
     }
-}
 
-#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
-pub(crate) enum GameState {
-    Playing,
-    Success(usize),
-    Failure,
+    /// Letters are white, except on unscored cells in light mode.
+    pub(crate) fn get_text_color(&self, palette: Palette) -> Color32 {
+        if *self == Self::Empty && !palette.dark_mode {
+            Color32::BLACK
+        } else {
+            Color32::WHITE
+        }
+    }
 }
 
 impl GameState {
-    pub(crate) fn get_state_label(&self, correct_word: &str) -> egui::Label {
+    pub(crate) fn get_state_text(&self, answers: &[&str]) -> String {
         match self {
-            Self::Playing => egui::Label::new(""),
-            Self::Success(attempts) => egui::Label::new(
-                RichText::new(format!("Success in {} tries!", attempts))
-                    .size(24.0)
-                    .strong()
-                    .color(Color32::DARK_GREEN),
-            ),
-            Self::Failure => egui::Label::new(
-                RichText::new(format!("The correct word was \"{}\".", correct_word))
-                    .size(24.0)
-                    .strong()
-                    .color(Color32::RED),
-            ),
+            Self::Playing => String::new(),
+            Self::Success(attempts) => format!("Success in {} tries!", attempts),
+            Self::Failure => match answers {
+                [answer] => format!("The correct word was \"{}\".", answer),
+                _ => format!("The correct words were {}.", answers.join(", ")),
+            },
+        }
+    }
+
+    pub(crate) fn get_state_label(&self, answers: &[&str]) -> egui::Label {
+        let text = RichText::new(self.get_state_text(answers)).size(24.0).strong();
+        match self {
+            Self::Playing => egui::Label::new(text),
+            Self::Success(_) => egui::Label::new(text.color(Color32::DARK_GREEN)),
+            Self::Failure => egui::Label::new(text.color(Color32::RED)),
         }
     }
 }
@@ -69,15 +78,138 @@ struct DebugMenu {
     open: bool,
     #[cfg_attr(feature = "persistence", serde(skip))]
     new_word: String,
+}
+
+/// How long the reason a guess was rejected stays on screen.
+const TOAST_SECONDS: f64 = 2.0;
+/// How many scorings the assistant may do per frame, so ranking doesn't freeze the window.
+const SOLVER_BUDGET: usize = 200_000;
+
+/// Options that apply to every new game.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+pub(crate) struct Settings {
     #[cfg_attr(feature = "persistence", serde(skip))]
-    focus: bool,
+    open: bool,
+    pub word_len: usize,
+    pub max_guesses: usize,
+    pub hard_mode: bool,
+    /// How many answers each game is played against at once.
+    pub boards: usize,
+    /// Put off choosing the answer for as long as possible, like Absurdle.
+    pub adversarial: bool,
+    pub timing: Timing,
+    /// How long timed games last, in seconds.
+    pub time_limit: u32,
+    pub show_assistant: bool,
+    /// The code of the language to play in.
+    pub language: String,
+    pub keyboard_layout: KeyboardLayout,
+    pub special_keys: SpecialKeys,
+    pub theme: Theme,
+    pub symbols: bool,
+    /// Turn off the tile animations.
+    pub reduced_motion: bool,
+    /// Where challenge links point, if not the public site. The web version always points at
+    /// itself.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub share_url: String,
+}
+
+impl Settings {
+    pub(crate) fn rules(&self) -> Rules {
+        Rules { max_guesses: self.max_guesses, hard_mode: self.hard_mode }
+    }
+
+    pub(crate) fn palette(&self, dark_mode: bool) -> Palette {
+        Palette { theme: self.theme, dark_mode, symbols: self.symbols }
+    }
+
+    pub(crate) fn language(&self) -> &'static Language {
+        Language::from_code(&self.language).unwrap_or(&language::ENGLISH)
+    }
+
+    /// Switch to `language`, keeping the word length if it has words that long.
+    pub(crate) fn set_language(&mut self, language: &Language) {
+        self.language = language.code.to_string();
+        let lens = language.word_lens();
+        self.word_len = self.word_len.clamp(*lens.start(), *lens.end());
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            open: false,
+            word_len: 5,
+            max_guesses: DEFAULT_MAX_GUESSES,
+            hard_mode: false,
+            boards: 1,
+            adversarial: false,
+            timing: Timing::Off,
+            time_limit: DEFAULT_TIME_LIMIT,
+            show_assistant: false,
+            language: language::ENGLISH.code.to_string(),
+            keyboard_layout: KeyboardLayout::default(),
+            special_keys: SpecialKeys::default(),
+            theme: Theme::default(),
+            symbols: false,
+            reduced_motion: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            share_url: String::new(),
+        }
+    }
 }
 
+/// Where the answer of the current game came from.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Mode {
+    Free,
+    Daily(u32),
+}
+
+#[derive(Default)]
+pub(crate) struct ShareWindow {
+    pub open: bool,
+    pub text: String,
+}
+
+/// Where the custom word lists came from, and why the last ones tried were rejected.
+#[derive(Default)]
+pub(crate) struct WordListWindow {
+    pub open: bool,
+    pub source: String,
+    pub rejected: String,
+    pub errors: Vec<String>,
+    /// Where the lists being downloaded come from, and the answer and guess downloads.
+    #[cfg(target_arch = "wasm32")]
+    pub download: Option<(String, utils::Download, Option<utils::Download>)>,
+}
+
+/// The most errors the word list window shows before summing up the rest.
+const MAX_WORD_LIST_ERRORS: usize = 20;
+
 #[derive(Default)]
 struct QrCodeWindow {
     open: bool,
+    /// The creator's message to put in the link.
+    message: String,
+    link: Option<String>,
     data: Vec<qrcode::Color>,
     width: usize,
+    /// What happened to the last image saved.
+    saved: Option<String>,
+}
+
+impl QrCodeWindow {
+    fn show_link(&mut self, link: Option<String>) {
+        self.link = link;
+        let (data, width) = self.link.as_deref().map(utils::gen_qrcode).unwrap_or_default();
+        self.data = data;
+        self.width = width;
+        self.saved = None;
+    }
 }
 
 #[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
@@ -94,6 +226,13 @@ impl WordleCell {
     }
 }
 
+/// A key of the on-screen keyboard, with the state its letter has reached on each board.
+#[derive(Debug)]
+pub(crate) struct KeyboardKey {
+    pub letter: char,
+    pub states: Vec<CellState>,
+}
+
 impl Default for WordleCell {
     fn default() -> Self {
         Self { state: CellState::Empty, letter: ' ' }
@@ -107,58 +246,118 @@ pub struct WordleApp {
     #[cfg_attr(feature = "persistence", serde(skip))]
     pub(crate) args: utils::Args,
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) word: String,
+    pub(crate) game: MultiGame,
+    /// The current game as it was last saved, replayed into `game` on startup.
+    saved_game: Option<SavedGame>,
+    /// The letters typed into the current row, not yet submitted to the game.
+    pub(crate) input: String,
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) cells: [[WordleCell; 5]; 6],
+    pub(crate) kbd_keydown: String,
+    /// Why the last submitted word was rejected, until the player types again.
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) next_cell: (usize, usize),
+    pub(crate) error: Option<GuessError>,
+    /// When the last word was rejected, in seconds of egui's clock, to time the toast and shake.
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) keyboard: ([WordleCell; 10], [WordleCell; 9], [WordleCell; 7]),
+    pub(crate) rejected_at: Option<f64>,
+    /// When the last guess was scored, to flip its tiles.
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) keyboard_state: HashMap<char, CellState>,
+    pub(crate) scored_at: Option<f64>,
+    /// When the last letter was typed, to pop its tile.
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) kbd_keydown: String,
+    pub(crate) typed_at: Option<f64>,
+    /// The clock of the current game.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) timer: Timer,
+    /// The clock of the current game as it was last saved, picked back up with the game.
+    saved_timer: Option<SavedTimer>,
+    /// The time of the last frame on egui's clock, which the timer is saved at.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    last_frame: f64,
+    pub(crate) mode: Mode,
+    /// The creator's message, if the game came from a challenge link with one.
+    pub(crate) challenge_message: Option<String>,
+    /// Why the challenge link or command line the game was opened with couldn't be played.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) link_error: Option<String>,
+    /// The most recent daily puzzle the player has started, so it can't be replayed.
+    pub(crate) last_daily: Option<u32>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    solver: Option<Solver>,
+    pub(crate) stats: Stats,
     #[cfg_attr(feature = "persistence", serde(skip))]
-    pub(crate) game_state: GameState,
+    pub(crate) stats_open: bool,
+    pub(crate) settings: Settings,
+    /// Word lists loaded at runtime, used instead of the built-in ones for their word length.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) custom_words: Option<Arc<WordList>>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) word_list_window: WordListWindow,
     debug_menu: DebugMenu,
     #[cfg_attr(feature = "persistence", serde(skip))]
     qrcode_window: QrCodeWindow,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) share_window: ShareWindow,
 }
 
 impl Default for WordleApp {
     fn default() -> Self {
         Self {
             args: Default::default(),
-            word: utils::get_random_word(),
-            cells: Default::default(),
-            next_cell: (0, 0),
-            #[rustfmt::skip] // rustfmt wants to split these chains into loads of tiny lines
-            keyboard: (
-                KBD_ROW1.chars().map(WordleCell::keyboard)
-                    .collect::<Vec<WordleCell>>().try_into().unwrap(),
-                KBD_ROW2.chars().map(WordleCell::keyboard)
-                    .collect::<Vec<WordleCell>>().try_into().unwrap(),
-                KBD_ROW3.chars().map(WordleCell::keyboard)
-                    .collect::<Vec<WordleCell>>().try_into().unwrap(),
-            ),
-            keyboard_state: LETTERS.chars().zip(std::iter::repeat(CellState::Empty)).collect(),
+            game: Game::new(&utils::get_random_word(5)).into(),
+            saved_game: None,
+            input: String::new(),
             kbd_keydown: String::default(),
-            game_state: GameState::Playing,
+            error: None,
+            rejected_at: None,
+            scored_at: None,
+            typed_at: None,
+            timer: Timer::default(),
+            saved_timer: None,
+            last_frame: 0.0,
+            mode: Mode::Free,
+            challenge_message: None,
+            link_error: None,
+            last_daily: None,
+            solver: None,
+            stats: Stats::default(),
+            stats_open: false,
+            settings: Settings::default(),
+            custom_words: None,
+            word_list_window: WordListWindow::default(),
             debug_menu: DebugMenu::default(),
             qrcode_window: QrCodeWindow::default(),
+            share_window: ShareWindow::default(),
+        }
+    }
+}
+
+impl WordleApp {
+    /// Also names the directory the saved state is kept in.
+    pub(crate) const NAME: &'static str = "Wordle Clone";
+
+    /// The current game as a challenge for someone else, with the message from the QR code
+    /// window. Challenges are a single word, so games on several boards or without an answer
+    /// yet can't be one.
+    fn challenge(&self) -> Option<Challenge> {
+        match self.game.boards() {
+            [game] if !game.is_adversarial() => Some(
+                Challenge::new(game.answer(), game.rules().clone())
+                    .with_message(&self.qrcode_window.message),
+            ),
+            _ => None,
         }
     }
 }
 
 impl epi::App for WordleApp {
     fn name(&self) -> &str {
-        "Wordle Clone"
+        Self::NAME
     }
 
     /// Called once before the first frame.
     fn setup(
         &mut self,
-        _ctx: &egui::Context,
+        ctx: &egui::Context,
         _frame: &epi::Frame,
         _storage: Option<&dyn epi::Storage>,
     ) {
@@ -172,8 +371,35 @@ impl epi::App for WordleApp {
         }
 
         self.args = args;
-        if let Some(word) = &self.args.word {
-            self.word = word.clone();
+        let language = self.args.language.as_deref().and_then(Language::from_code);
+        self.settings.set_language(language.unwrap_or_else(|| self.settings.language()));
+        self.load_word_lists_from_args();
+        self.link_error = self.args.link_error.clone();
+        let saved_game = self.saved_game.take().and_then(|saved| {
+            let words = self.words_for(saved.answer.chars().count());
+            MultiGame::restore_with_words(&saved, words).ok()
+        });
+        let saved_timer = self.saved_timer.take();
+        // Saved games are always played with the built-in lists, so asking for custom ones starts
+        // a new game instead
+        let builtin = self.custom_words.is_none() && !self.word_lists_pending();
+        let restored = match saved_game.filter(|_| builtin) {
+            Some(game) => {
+                self.game = game;
+                let now = ctx.input().time;
+                self.timer =
+                    saved_timer.map(|saved| Timer::restore(&saved, now)).unwrap_or_default();
+                true
+            }
+            None => false,
+        };
+        // A challenge link or the command line replaces whatever was being played, except a
+        // daily puzzle asked for again. Word lists still on their way start it once they arrive.
+        if self.word_lists_pending() || (!self.start_from_args() && !restored) {
+            self.reset_random_word();
+        }
+        if self.input.chars().count() > self.game.word_len() || self.game.is_over() {
+            self.input.clear();
         }
     }
 
@@ -181,35 +407,68 @@ impl epi::App for WordleApp {
     /// Note that you must enable the `persistence` feature for this to work.
     #[cfg(feature = "persistence")]
     fn save(&mut self, storage: &mut dyn epi::Storage) {
+        self.saved_game = Some(self.game.save());
+        self.saved_timer = Some(self.timer.save(self.last_frame));
         epi::set_value(storage, epi::APP_KEY, self);
     }
 
     /// Called each time the UI needs repainting, which may be many times per second.
     /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
     fn update(&mut self, ctx: &egui::Context, frame: &epi::Frame) {
+        let palette = self.settings.palette(ctx.style().visuals.dark_mode);
+        let now = ctx.input().time;
+        self.last_frame = now;
+        // Checked before any text field can take or give up focus this frame, so the Enter that
+        // finishes typing in one isn't also taken as a guess
+        let typing = ctx.wants_keyboard_input();
+        self.check_timer(now);
+        if self.timer.is_running() {
+            ctx.request_repaint();
+        }
+
+        // A word list file dropped onto the window is played with straight away
+        let dropped_files = ctx.input().raw.dropped_files.clone();
+        for file in dropped_files {
+            let text = match (&file.bytes, &file.path) {
+                (Some(bytes), _) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+                (None, Some(path)) => std::fs::read_to_string(path),
+                (None, None) => continue,
+            };
+            let name = match &file.path {
+                Some(path) => path.display().to_string(),
+                None => file.name.clone(),
+            };
+            match text {
+                Ok(text) => self.use_word_lists(name, &text, None),
+                Err(err) => self.show_word_list_errors(name, vec![err.to_string()]),
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        if self.poll_word_lists() {
+            ctx.request_repaint();
+        }
+
         if self.debug_menu.open {
             let mut debug_open = true;
             egui::Window::new("Debug Menu")
                 .open(&mut debug_open)
                 .default_pos(ctx.available_rect().right_top())
                 .show(ctx, |ui| {
-                    ui.label(format!("Current word: {}", self.word));
-                    ui.label(format!(
-                        "Current word (base64): {}",
-                        utils::encode(self.word.clone()),
-                    ));
-                    ui.label(format!(
-                        "Current word (base64 decoded): {}",
-                        utils::decode(utils::encode(self.word.clone())).unwrap(),
-                    ));
+                    ui.label(format!("Current word: {}", self.game.answers().join(", ")));
+                    if let [game] = self.game.boards() {
+                        let challenge = Challenge::new(game.answer(), game.rules().clone());
+                        ui.label(format!("Challenge code: {}", challenge.encode()));
+                    }
 
                     let response = ui.text_edit_singleline(&mut self.debug_menu.new_word);
-                    self.debug_menu.focus = response.has_focus();
-                    if response.lost_focus()
-                        && ui.input().key_pressed(Key::Enter)
-                        && crate::WORD_LIST.contains(&&*self.debug_menu.new_word.to_uppercase())
-                    {
-                        self.word = self.debug_menu.new_word.to_uppercase();
+                    let new_word =
+                        self.settings.language().fold_word(&self.debug_menu.new_word).ok();
+                    if response.lost_focus() && ui.input().key_pressed(Key::Enter) {
+                        if let Some(word) = new_word
+                            .filter(|word| self.words_for(word.chars().count()).is_guess(word))
+                        {
+                            self.new_game(&word);
+                        }
                     }
 
                     if ui.button("Reset").clicked() {
@@ -224,14 +483,251 @@ impl epi::App for WordleApp {
             }
         }
 
+        if self.settings.open {
+            let mut settings_open = true;
+            egui::Window::new("Settings").open(&mut settings_open).show(ctx, |ui| {
+                let mut new_language = None;
+                egui::ComboBox::from_label("Language")
+                    .selected_text(self.settings.language().name)
+                    .show_ui(ui, |ui| {
+                        for language in language::LANGUAGES {
+                            let selected = language.code == self.settings.language;
+                            if ui.selectable_label(selected, language.name).clicked() && !selected {
+                                new_language = Some(language);
+                            }
+                        }
+                    });
+                if let Some(language) = new_language {
+                    self.settings.set_language(language);
+                    // Custom word lists were checked against the old alphabet
+                    self.custom_words = None;
+                    self.reset_random_word();
+                }
+                egui::ComboBox::from_label("Keyboard")
+                    .selected_text(self.settings.keyboard_layout.name())
+                    .show_ui(ui, |ui| {
+                        for layout in KeyboardLayout::ALL {
+                            ui.selectable_value(
+                                &mut self.settings.keyboard_layout,
+                                layout,
+                                layout.name(),
+                            );
+                        }
+                    });
+                egui::ComboBox::from_label("Colors")
+                    .selected_text(self.settings.theme.name())
+                    .show_ui(ui, |ui| {
+                        for theme in Theme::ALL {
+                            ui.selectable_value(&mut self.settings.theme, theme, theme.name());
+                        }
+                    });
+                ui.checkbox(&mut self.settings.symbols, "Mark letters with symbols");
+                ui.checkbox(&mut self.settings.reduced_motion, "Reduce motion");
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    ui.horizontal(|ui| {
+                        ui.label("Link address");
+                        // Letters typed here stay out of the guess, see `typing` in `update`
+                        let address = egui::TextEdit::singleline(&mut self.settings.share_url)
+                            .hint_text(challenge::DEFAULT_LINK_BASE);
+                        ui.add(address);
+                    });
+                    let share_url = self.settings.share_url.trim();
+                    if self.args.share_url.is_some() {
+                        ui.small("The link address from the command line is used for now.");
+                    } else if !share_url.is_empty() && !challenge::is_link_base(share_url) {
+                        ui.colored_label(
+                            Color32::from_rgb(230, 126, 34),
+                            "Links need an http:// or https:// address.",
+                        );
+                    }
+                }
+                egui::ComboBox::from_label("Enter and delete keys")
+                    .selected_text(self.settings.special_keys.name())
+                    .show_ui(ui, |ui| {
+                        for keys in SpecialKeys::ALL {
+                            ui.selectable_value(&mut self.settings.special_keys, keys, keys.name());
+                        }
+                    });
+
+                // A custom word list decides the length itself, as does a language with words
+                // of only one length
+                let lens = self.settings.language().word_lens();
+                let response = ui.add_enabled(
+                    self.custom_words.is_none() && lens.start() != lens.end(),
+                    egui::Slider::new(&mut self.settings.word_len, lens).text("Letters"),
+                ) | ui.add(
+                    egui::Slider::new(&mut self.settings.max_guesses, MIN_GUESSES..=MAX_GUESSES)
+                        .text("Guesses"),
+                ) | ui.checkbox(&mut self.settings.hard_mode, "Hard mode")
+                    | ui.checkbox(&mut self.settings.adversarial, "Adversarial (Absurdle)");
+                let mut boards_changed = false;
+                // An adversarial game has only the one board to dodge the player on
+                ui.add_enabled_ui(!self.settings.adversarial, |ui| {
+                    egui::ComboBox::from_label("Boards")
+                        .selected_text(self.settings.boards.to_string())
+                        .show_ui(ui, |ui| {
+                            for boards in BOARD_COUNTS {
+                                boards_changed |= ui
+                                    .selectable_value(
+                                        &mut self.settings.boards,
+                                        boards,
+                                        boards.to_string(),
+                                    )
+                                    .changed();
+                            }
+                        });
+                });
+                if self.settings.boards > 1 && !self.settings.adversarial {
+                    ui.small(format!(
+                        "{} guesses with {} boards",
+                        engine::scaled_max_guesses(self.settings.max_guesses, self.settings.boards),
+                        self.settings.boards
+                    ));
+                }
+                let mut timing_changed = false;
+                egui::ComboBox::from_label("Timer")
+                    .selected_text(self.settings.timing.name())
+                    .show_ui(ui, |ui| {
+                        for timing in Timing::ALL {
+                            timing_changed |= ui
+                                .selectable_value(&mut self.settings.timing, timing, timing.name())
+                                .changed();
+                        }
+                    });
+                let time_limit = ui.add_enabled(
+                    self.settings.timing != Timing::Off,
+                    egui::Slider::new(
+                        &mut self.settings.time_limit,
+                        MIN_TIME_LIMIT..=MAX_TIME_LIMIT,
+                    )
+                    .text("Seconds"),
+                );
+                // The rules are fixed for the length of a game, so start over straight away
+                if response.changed() || boards_changed || timing_changed || time_limit.changed() {
+                    self.reset_random_word();
+                }
+
+                ui.separator();
+                match &self.custom_words {
+                    Some(words) => {
+                        ui.label(format!(
+                            "Word list: {} ({} answers, {} guesses)",
+                            self.word_list_window.source,
+                            words.answers().len(),
+                            words.guesses().len()
+                        ));
+                        if ui.button("Use built-in word list").clicked() {
+                            self.use_builtin_word_lists();
+                        }
+                    }
+                    None => {
+                        ui.label("Word list: built-in");
+                    }
+                }
+                ui.small("Drop a word list file onto the window to play with it.");
+            });
+            if !settings_open {
+                self.settings.open = false;
+            }
+        }
+
+        if let Some(error) = &self.link_error {
+            let mut open = true;
+            egui::Window::new("Couldn't start that game").open(&mut open).collapsible(false).show(
+                ctx,
+                |ui| {
+                    ui.colored_label(Color32::from_rgb(230, 126, 34), error);
+                    ui.label("Another game was started instead.");
+                },
+            );
+            if !open {
+                self.link_error = None;
+            }
+        }
+
+        if self.word_list_window.open {
+            let WordListWindow { open, rejected, errors, .. } = &mut self.word_list_window;
+            egui::Window::new("Word list").open(open).collapsible(false).show(ctx, |ui| {
+                ui.label(format!("Couldn't use {}:", rejected));
+                for error in errors.iter().take(MAX_WORD_LIST_ERRORS) {
+                    ui.colored_label(Color32::from_rgb(230, 126, 34), error);
+                }
+                if errors.len() > MAX_WORD_LIST_ERRORS {
+                    ui.label(format!("and {} more", errors.len() - MAX_WORD_LIST_ERRORS));
+                }
+                ui.small("Lists need one word per line, all the same length, with no repeats.");
+            });
+        }
+
+        if self.stats_open {
+            let last_win = match self.game.state() {
+                GameState::Success(guesses) => Some(*guesses),
+                _ => None,
+            };
+            egui::Window::new("Statistics")
+                .open(&mut self.stats_open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    draw::draw_stats(ui, &self.stats, last_win, palette);
+                });
+        }
+
+        if self.share_window.open {
+            egui::Window::new("Share").open(&mut self.share_window.open).collapsible(false).show(
+                ctx,
+                |ui| {
+                    ui.label("Copied to the clipboard. If it didn't work, copy it from here:");
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.share_window.text.as_str())
+                            .desired_rows(self.share_window.text.lines().count()),
+                    );
+                    if ui.button("Copy").clicked() {
+                        ui.output().copied_text = self.share_window.text.clone();
+                    }
+                },
+            );
+        }
+
         if self.qrcode_window.open {
+            let mut message_changed = false;
+            let mut qr_image = None;
             egui::Window::new("QR Code")
                 .open(&mut self.qrcode_window.open)
                 .default_pos(ctx.available_rect().center())
                 .default_size((200.0, 200.0))
                 .show(ctx, |ui| {
+                    let message = egui::TextEdit::singleline(&mut self.qrcode_window.message)
+                        .hint_text("Add a message (optional)");
+                    message_changed = ui.add(message).changed();
+                    if let Some(link) = &self.qrcode_window.link {
+                        let (data, width) = (&self.qrcode_window.data, self.qrcode_window.width);
+                        ui.horizontal(|ui| {
+                            if ui.button("Copy link").clicked() {
+                                ui.output().copied_text = link.clone();
+                            }
+                            if ui.button("Save as PNG").clicked() {
+                                qr_image = Some(("wordle-challenge.png", qr::to_png(data, width)));
+                            }
+                            if ui.button("Save as SVG").clicked() {
+                                let svg = qr::to_svg(data, width).into_bytes();
+                                qr_image = Some(("wordle-challenge.svg", svg));
+                            }
+                        });
+                    }
+                    if let Some(saved) = &self.qrcode_window.saved {
+                        ui.small(saved);
+                    }
                     draw::draw_qr_code(ui, &self.qrcode_window.data, self.qrcode_window.width);
                 });
+            if message_changed {
+                let link = self.challenge().and_then(|challenge| self.challenge_link(&challenge));
+                self.qrcode_window.show_link(link);
+            }
+            if let Some((name, bytes)) = qr_image {
+                self.qrcode_window.saved = utils::save_file(name, &bytes);
+            }
         }
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
@@ -241,12 +737,39 @@ impl epi::App for WordleApp {
                     if ui.button("Quit").clicked() {
                         frame.quit();
                     }
-                    if ui.button("Show QR Code").clicked() {
-                        let code = utils::gen_qrcode(self.word.clone());
-                        self.qrcode_window.data = code.0;
-                        self.qrcode_window.width = code.1;
+                    if ui.button("New game").clicked() {
+                        self.reset_random_word();
+                        ui.close_menu();
+                    }
+                    let today = daily::todays_puzzle();
+                    if ui
+                        .add_enabled(
+                            self.last_daily != Some(today),
+                            egui::Button::new(format!("Daily puzzle #{}", today)),
+                        )
+                        .on_disabled_hover_text("Already played today")
+                        .clicked()
+                    {
+                        self.start_daily();
+                        ui.close_menu();
+                    }
+                    let challenge = self.challenge();
+                    if ui
+                        .add_enabled(challenge.is_some(), egui::Button::new("Show QR Code"))
+                        .on_disabled_hover_text("Challenges have one board and a set answer")
+                        .clicked()
+                    {
+                        let link = challenge.and_then(|challenge| self.challenge_link(&challenge));
+                        self.qrcode_window.show_link(link);
                         self.qrcode_window.open = !self.qrcode_window.open;
                     }
+                    if ui.button("Statistics").clicked() {
+                        self.stats_open = !self.stats_open;
+                    }
+                    if ui.button("Settings").clicked() {
+                        self.settings.open = !self.settings.open;
+                    }
+                    ui.checkbox(&mut self.settings.show_assistant, "Show assistant");
                     ui.checkbox(&mut self.debug_menu.open, "Show debug menu");
                 });
                 ui.with_layout(Layout::right_to_left(), |ui| {
@@ -255,11 +778,31 @@ impl epi::App for WordleApp {
             });
         });
 
+        if self.settings.show_assistant {
+            egui::SidePanel::right("assistant").show(ctx, |ui| {
+                let game = match self.game.boards() {
+                    [game] => game,
+                    _ => {
+                        ui.heading("Assistant");
+                        ui.label("The assistant only helps with a single board.");
+                        return;
+                    }
+                };
+                let solver = match &mut self.solver {
+                    Some(solver) if solver.is_for(game) => solver,
+                    solver => solver.insert(Solver::new(game)),
+                };
+                if !solver.is_done() {
+                    solver.step(SOLVER_BUDGET);
+                    ctx.request_repaint();
+                }
+                draw::draw_assistant(ui, solver, palette);
+            });
+        }
+
         egui::CentralPanel::default().show(ctx, |ui| {
             // The central panel the region left after adding TopPanel's and SidePanel's
-            if matches!(self.game_state, GameState::Playing)
-                && (!self.debug_menu.open || !self.debug_menu.focus)
-            {
+            if matches!(self.game.state(), GameState::Playing) && !typing {
                 self.handle_keys(ui)
             }
             // If there was a key found, clear it
@@ -269,11 +812,50 @@ impl epi::App for WordleApp {
 
             ui.vertical_centered(|ui| {
                 ui.set_width(ui.available_width());
-                draw::draw_letters(ui, &self.cells);
-                draw::draw_game_end_message(ui, &self.game_state, &self.word);
-                draw::draw_keyboard(ui, &self.keyboard, &mut self.kbd_keydown);
+                if let Mode::Daily(number) = self.mode {
+                    if draw::draw_daily_status(ui, number, self.game.is_over()) {
+                        self.start_daily();
+                    }
+                }
+                if let Some(message) = &self.challenge_message {
+                    ui.label(RichText::new(format!("“{}”", message)).italics());
+                }
+                if let Some(left) = self.candidates_left() {
+                    ui.label(left);
+                }
+                if let Some(clock) = self.clock_text(now) {
+                    ui.label(RichText::new(clock).size(20.0).strong().monospace());
+                }
+                let animations = (!self.settings.reduced_motion).then(|| self.board_animations(ui));
+                let word_len = self.game.word_len();
+                if animations.iter().flatten().any(|animation| animation.is_running(word_len)) {
+                    ctx.request_repaint();
+                }
+                draw::draw_letters(ui, &self.boards(), palette, animations.as_deref());
+                draw::draw_game_end_message(ui, self.game.state(), &self.game.answers());
+                if let Some(splits) = self.splits_text() {
+                    ui.small(splits);
+                }
+                if self.game.is_over() && ui.button("Share").clicked() {
+                    self.share(ctx, palette.dark_mode);
+                }
+                draw::draw_keyboard(
+                    ui,
+                    &self.keyboard(),
+                    self.settings.special_keys,
+                    palette,
+                    &mut self.kbd_keydown,
+                );
             });
             egui::warn_if_debug_build(ui);
         });
+
+        match (&self.error, self.rejected_at) {
+            (Some(error), Some(at)) if ctx.input().time - at < TOAST_SECONDS => {
+                draw::draw_toast(ctx, &error.to_string(), palette);
+                ctx.request_repaint();
+            }
+            _ => {}
+        }
     }
 }
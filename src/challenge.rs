@@ -0,0 +1,251 @@
+//! The payload of challenge links: a game set up by one player for another to solve.
+//!
+//! A payload is a version byte, a flags byte, the word length and the guess limit, then the word
+//! and the creator's message as UTF-8, and a checksum of everything before it. The text is masked
+//! so the word can't be read straight out of the link, and the whole thing is URL-safe base64.
+
+use crate::engine::{Rules, MAX_GUESSES, MIN_GUESSES};
+use crate::language::{Language, ENGLISH};
+use crate::utils::Args;
+use std::fmt;
+
+/// The query parameters for the challenge code and the language, in the links the game makes and
+/// reads.
+pub const CODE_PARAM: &str = "c";
+pub const LANGUAGE_PARAM: &str = "lang";
+/// Where the game is published, for links made outside a browser.
+pub const DEFAULT_LINK_BASE: &str = "https://dacid44.github.io/wordle_clone/";
+
+const VERSION: u8 = 1;
+const HARD_MODE: u8 = 1 << 0;
+const HAS_MESSAGE: u8 = 1 << 1;
+const HEADER_LEN: usize = 4;
+const CHECKSUM_LEN: usize = 2;
+/// Links that were cut short can end part way through a byte. The checksum catches those anyway,
+/// with a clearer error than base64 would give.
+const BASE64: base64::Config = base64::URL_SAFE_NO_PAD.decode_allow_trailing_bits(true);
+/// The longest creator message, in characters, that a link is made with.
+pub const MAX_MESSAGE_LEN: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub word: String,
+    pub rules: Rules,
+    /// A note from whoever made the challenge, shown while it's played.
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+    /// The link has characters base64 doesn't use.
+    NotBase64,
+    /// The payload ends before the word does.
+    Truncated,
+    /// The checksum doesn't match, so part of the link was changed or lost.
+    Checksum,
+    /// The link was made by a newer version of the game.
+    UnknownVersion(u8),
+    /// The checksum matches but the contents don't make sense.
+    Malformed,
+    /// The guess limit is outside what games allow.
+    GuessLimit(usize),
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotBase64 => write!(f, "This challenge link is damaged: it isn't valid base64"),
+            Self::Truncated => write!(f, "This challenge link is incomplete"),
+            Self::Checksum => write!(f, "This challenge link is damaged: its checksum is wrong"),
+            Self::UnknownVersion(version) => write!(
+                f,
+                "This challenge link was made by a newer version of the game (format {})",
+                version
+            ),
+            Self::Malformed => {
+                write!(f, "This challenge link is damaged: its contents are invalid")
+            }
+            Self::GuessLimit(max_guesses) => write!(
+                f,
+                "This challenge link allows {} guesses, but games allow {} to {}",
+                max_guesses, MIN_GUESSES, MAX_GUESSES
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChallengeError {}
+
+impl Challenge {
+    pub fn new(word: &str, rules: Rules) -> Self {
+        Self { word: word.to_string(), rules, message: None }
+    }
+
+    /// Add a message from the creator. Blank messages are left out, and long ones are cut to
+    /// `MAX_MESSAGE_LEN` characters.
+    pub fn with_message(mut self, message: &str) -> Self {
+        let message = message.trim();
+        self.message =
+            (!message.is_empty()).then(|| message.chars().take(MAX_MESSAGE_LEN).collect());
+        self
+    }
+
+    /// The payload for a link.
+    pub fn encode(&self) -> String {
+        let mut flags = 0;
+        if self.rules.hard_mode {
+            flags |= HARD_MODE;
+        }
+        if self.message.is_some() {
+            flags |= HAS_MESSAGE;
+        }
+        let len = self.word.chars().count().min(u8::MAX as usize) as u8;
+        let max_guesses = self.rules.max_guesses.min(u8::MAX as usize) as u8;
+        let mut bytes = vec![VERSION, flags, len, max_guesses];
+        let mut text = self.word.clone();
+        text += self.message.as_deref().unwrap_or("");
+        bytes.extend(mask(text.into_bytes()));
+        bytes.extend(checksum(&bytes).to_be_bytes());
+        base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+    }
+
+    /// Read a payload made by `encode`.
+    pub fn decode(code: &str) -> Result<Self, ChallengeError> {
+        let bytes = base64::decode_config(code.trim(), BASE64).map_err(|err| match err {
+            base64::DecodeError::InvalidLength => ChallengeError::Truncated,
+            _ => ChallengeError::NotBase64,
+        })?;
+        if bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+            return Err(ChallengeError::Truncated);
+        }
+        let (bytes, sum) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        if checksum(bytes).to_be_bytes() != sum {
+            return Err(ChallengeError::Checksum);
+        }
+        let (header, text) = bytes.split_at(HEADER_LEN);
+        let (version, flags, len, max_guesses) = (header[0], header[1], header[2], header[3]);
+        if version > VERSION {
+            return Err(ChallengeError::UnknownVersion(version));
+        } else if version != VERSION {
+            return Err(ChallengeError::Malformed);
+        }
+        let text = String::from_utf8(mask(text.to_vec())).map_err(|_| ChallengeError::Malformed)?;
+        let split = text.char_indices().nth(len as usize).map_or(text.len(), |(i, _)| i);
+        let (word, message) = text.split_at(split);
+        if word.chars().count() < len as usize {
+            return Err(ChallengeError::Truncated);
+        }
+        if len == 0 || (flags & HAS_MESSAGE == 0) != message.is_empty() {
+            return Err(ChallengeError::Malformed);
+        }
+        let max_guesses = max_guesses as usize;
+        if !(MIN_GUESSES..=MAX_GUESSES).contains(&max_guesses) {
+            return Err(ChallengeError::GuessLimit(max_guesses));
+        }
+        Ok(Self {
+            word: word.to_string(),
+            rules: Rules { max_guesses, hard_mode: flags & HARD_MODE != 0 },
+            message: (!message.is_empty()).then(|| message.to_string()),
+        })
+    }
+
+    /// Read an old `bword` link, which held only the word, base64 encoded five times over.
+    pub fn decode_legacy(code: &str) -> Result<Self, ChallengeError> {
+        let mut word = code.trim().to_string();
+        for _ in 0..5 {
+            let bytes = base64::decode_config(&word, base64::URL_SAFE_NO_PAD)
+                .map_err(|_| ChallengeError::NotBase64)?;
+            word = String::from_utf8_lossy(&bytes).into();
+        }
+        if word.is_empty() {
+            return Err(ChallengeError::Truncated);
+        }
+        Ok(Self::new(&word.to_uppercase(), Rules::default()))
+    }
+}
+
+/// Scramble or unscramble the text of a payload, so the word doesn't show up in the link.
+fn mask(mut bytes: Vec<u8>) -> Vec<u8> {
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte ^= (i as u8).wrapping_mul(73).wrapping_add(0x5a);
+    }
+    bytes
+}
+
+/// Fletcher-16, but starting from one like Adler-32 so that a link of all zeros doesn't pass.
+fn checksum(bytes: &[u8]) -> u16 {
+    let (mut a, mut b) = (1u16, 0u16);
+    for &byte in bytes {
+        a = (a + byte as u16) % 255;
+        b = (b + a) % 255;
+    }
+    b << 8 | a
+}
+
+/// Whether challenges can be added to `base` to make links.
+pub fn is_link_base(base: &str) -> bool {
+    (base.starts_with("https://") || base.starts_with("http://")) && !base.contains('#')
+}
+
+/// The link to play `challenge` in `language` with the game at `base`.
+pub fn link(base: &str, challenge: &Challenge, language: &Language) -> String {
+    let separator = if base.contains('?') { '&' } else { '?' };
+    let mut url = format!("{}{}{}={}", base, separator, CODE_PARAM, challenge.encode());
+    if language.code != ENGLISH.code {
+        url += &format!("&{}={}", LANGUAGE_PARAM, language.code);
+    }
+    url
+}
+
+/// The game a link asks for, given the values of its query parameters.
+pub fn link_args(param: impl Fn(&str) -> Option<String>) -> Args {
+    // New links carry everything in one checksummed payload; old ones had just the word
+    let challenge = if let Some(code) = param(CODE_PARAM) {
+        Some(Challenge::decode(&code))
+    } else if let Some(code) = param("bword") {
+        Some(Challenge::decode_legacy(&code))
+    } else {
+        param("word").map(|word| Ok(Challenge::new(&word.to_uppercase(), Rules::default())))
+    };
+    let (challenge, mut link_error) = match challenge {
+        Some(Ok(challenge)) => (Some(challenge), None),
+        Some(Err(err)) => (None, Some(err.to_string())),
+        None => (None, None),
+    };
+
+    let lang = param(LANGUAGE_PARAM);
+    let language = lang.as_deref().and_then(Language::from_code).unwrap_or(&ENGLISH);
+    // With word lists of its own the word is checked against them once they've been fetched
+    let answers = param("answer_list");
+    let word = challenge.as_ref().and_then(|challenge| {
+        let word = language.fold_word(&challenge.word).ok().filter(|word| {
+            answers.is_some()
+                || language.word_list(word.chars().count()).is_some_and(|w| w.is_guess(word))
+        });
+        if word.is_none() {
+            link_error =
+                Some(format!("The challenge word isn't in the {} word list", language.name));
+        }
+        word
+    });
+    // Old links gave the rules as separate parameters; new ones only go by their payload
+    let legacy = |name| param(CODE_PARAM).is_none().then(|| param(name)).flatten();
+    let max_guesses = legacy("guesses")
+        .and_then(|x| x.parse().ok())
+        .filter(|x| (MIN_GUESSES..=MAX_GUESSES).contains(x));
+    let max_guesses = max_guesses.or_else(|| Some(challenge.as_ref()?.rules.max_guesses));
+    let hard_mode = legacy("hard").map(|x| x == "1");
+    let hard_mode = hard_mode.or_else(|| Some(challenge.as_ref()?.rules.hard_mode));
+    Args {
+        word,
+        max_guesses,
+        hard_mode,
+        // URLs of custom word lists, fetched once the app starts
+        answers,
+        guesses: param("guess_list"),
+        language: lang,
+        message: challenge.and_then(|challenge| challenge.message),
+        link_error,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,347 @@
+//! The command line of the native game.
+
+use crate::challenge::{self, Challenge, ChallengeError};
+use crate::engine::{BOARD_COUNTS, MAX_GUESSES, MIN_GUESSES};
+use crate::language::{Language, ENGLISH};
+use crate::timer::{Timing, MAX_TIME_LIMIT, MIN_TIME_LIMIT};
+use crate::utils::Args;
+use crate::words::WordList;
+use std::fmt;
+
+pub const USAGE: &str = "\
+Usage: wordle_clone [OPTIONS] [WORD]
+
+Play Wordle in a window, or in the terminal with --tui.
+
+Options:
+  --word <WORD>          Play with WORD as the answer (the same as giving WORD on its own)
+  --bword <CODE>         Play the challenge in a link's code, old or new style
+  --seed <NUMBER>        Pick the answer at random, the same way every time for a NUMBER
+  --daily                Play today's daily puzzle
+  --hard                 Revealed letters must be used in every later guess
+  --length <LETTERS>     How many letters the answer has
+  --max-guesses <COUNT>  How many guesses there are (2 to 12)
+  --boards <COUNT>       Play against 1, 2, 4 or 8 answers at once, with a guess more per board
+  --absurdle             Play against a game that avoids picking an answer for as long as it can
+  --countdown <SECONDS>  Solve each word within SECONDS (30 to 900)
+  --speedrun <SECONDS>   Solve as many words as possible in SECONDS (30 to 900)
+  --lang <CODE>          Play in another language: en, es, de or fi
+  --answers <FILE>       Pick answers from FILE, one word per line
+  --guesses <FILE>       Accept the guesses in FILE, one word per line
+  --share-url <URL>      Point challenge links at the game at URL, such as a mirror
+  --tui                  Play in the terminal instead of a window
+  -h, --help             Show this help
+
+Words are checked against the English word list unless --lang is given. The link address can
+also be set with the WORDLE_SHARE_URL environment variable, or in the settings.";
+
+/// The environment variable for the link address, used when `--share-url` isn't given.
+pub const SHARE_URL_VAR: &str = "WORDLE_SHARE_URL";
+
+/// What the command line asked for.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Show `USAGE` instead of playing.
+    pub help: bool,
+    pub tui: bool,
+    pub args: Args,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    UnknownOption(String),
+    MissingValue(&'static str),
+    /// A value that isn't allowed for an option, and what would be.
+    InvalidValue {
+        option: &'static str,
+        value: String,
+        expected: String,
+    },
+    /// Two options that ask for different games.
+    Conflict(&'static str, &'static str),
+    /// An option that only works with another one.
+    Requires(&'static str, &'static str),
+    /// A word that isn't in the word list of the language it's played in.
+    NotAWord {
+        word: String,
+        language: &'static str,
+    },
+    /// A `--bword` code that couldn't be read.
+    BadCode(ChallengeError),
+    /// More than one word given on its own.
+    ExtraArgument(String),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownOption(option) => write!(f, "Unknown option {}", option),
+            Self::MissingValue(option) => write!(f, "{} needs a value", option),
+            Self::InvalidValue { option, value, expected } => {
+                write!(f, "Invalid value \"{}\" for {}: expected {}", value, option, expected)
+            }
+            Self::Conflict(first, second) => write!(f, "{} can't be used with {}", first, second),
+            Self::Requires(first, second) => write!(f, "{} needs {} too", first, second),
+            Self::NotAWord { word, language } => {
+                write!(f, "\"{}\" isn't in the {} word list", word, language)
+            }
+            Self::BadCode(err) => write!(f, "--bword: {}", err),
+            Self::ExtraArgument(arg) => write!(f, "Unexpected argument \"{}\"", arg),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Read the command line, without the program name.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options, CliError> {
+    let mut args = args.into_iter();
+    let mut tui = false;
+    let mut daily = false;
+    let mut hard = false;
+    let mut adversarial = false;
+    let mut timing: Option<(&'static str, Timing, usize)> = None;
+    let mut word = None;
+    let mut code = None;
+    let mut seed = None;
+    let mut len = None;
+    let mut max_guesses = None;
+    let mut boards = None;
+    let mut lang = None;
+    let mut answers = None;
+    let mut guesses = None;
+    let mut share_url = None;
+
+    while let Some(arg) = args.next() {
+        // Values can be given as `--option value` or `--option=value`
+        let (option, inline) = match arg.split_once('=') {
+            Some((option, value)) if arg.starts_with("--") => (option.to_string(), Some(value)),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |option: &'static str| match inline {
+            Some(value) => Ok(value.to_string()),
+            None => args.next().ok_or(CliError::MissingValue(option)),
+        };
+        match option.as_str() {
+            "-h" | "--help" => return Ok(Options { help: true, ..Default::default() }),
+            "--tui" => tui = true,
+            "--daily" => daily = true,
+            "--hard" => hard = true,
+            "--absurdle" => adversarial = true,
+            "--countdown" | "--speedrun" => {
+                let (option, mode) = match option.as_str() {
+                    "--countdown" => ("--countdown", Timing::Countdown),
+                    _ => ("--speedrun", Timing::Speedrun),
+                };
+                let seconds = number(option, value(option)?, "a number of seconds")?;
+                let seconds =
+                    in_range(option, seconds, MIN_TIME_LIMIT as usize, MAX_TIME_LIMIT as usize)?;
+                if let Some((other, _, _)) = timing.filter(|&(other, _, _)| other != option) {
+                    return Err(CliError::Conflict(other, option));
+                }
+                timing = Some((option, mode, seconds));
+            }
+            "--word" => word = Some(value("--word")?),
+            "--bword" => code = Some(value("--bword")?),
+            "--seed" => seed = Some(number("--seed", value("--seed")?, "a whole number")?),
+            "--length" => len = Some(number("--length", value("--length")?, "a number")?),
+            "--max-guesses" => {
+                let count = number("--max-guesses", value("--max-guesses")?, "a number")?;
+                max_guesses = Some(in_range("--max-guesses", count, MIN_GUESSES, MAX_GUESSES)?);
+            }
+            "--boards" => {
+                let count = number("--boards", value("--boards")?, "a number")?;
+                if !BOARD_COUNTS.contains(&count) {
+                    return Err(CliError::InvalidValue {
+                        option: "--boards",
+                        value: count.to_string(),
+                        expected: "1, 2, 4 or 8".to_string(),
+                    });
+                }
+                boards = Some(count);
+            }
+            "--lang" => lang = Some(value("--lang")?),
+            "--answers" => answers = Some(value("--answers")?),
+            "--guesses" => guesses = Some(value("--guesses")?),
+            "--share-url" => share_url = Some(("--share-url", value("--share-url")?)),
+            _ if option.starts_with('-') && option.len() > 1 => {
+                return Err(CliError::UnknownOption(option))
+            }
+            _ if word.is_some() => return Err(CliError::ExtraArgument(arg)),
+            _ => word = Some(arg),
+        }
+    }
+
+    let language = match &lang {
+        Some(code) => Language::from_code(code).ok_or_else(|| CliError::InvalidValue {
+            option: "--lang",
+            value: code.clone(),
+            expected: "one of en, es, de or fi".to_string(),
+        })?,
+        None => &ENGLISH,
+    };
+    if guesses.is_some() && answers.is_none() {
+        return Err(CliError::Requires("--guesses", "--answers"));
+    }
+    // Custom answers can be any length the game supports, built-in ones only what there are
+    let lens = match answers {
+        Some(_) => crate::MIN_WORD_LEN..=crate::MAX_WORD_LEN,
+        None => language.word_lens(),
+    };
+    if let Some(len) = len {
+        in_range("--length", len, *lens.start(), *lens.end())?;
+    }
+    // Words are checked against the custom lists the app will play with, if it can use them
+    let custom =
+        answers.as_deref().and_then(|answers| read_word_lists(language, answers, &guesses));
+    let is_guess = |word: &str| match &custom {
+        Some(words) if words.word_len() == word.chars().count() => words.is_guess(word),
+        _ => is_word(language, word),
+    };
+
+    let mut message = None;
+    if let Some(code) = code {
+        if word.is_some() {
+            return Err(CliError::Conflict("--bword", "--word"));
+        }
+        let challenge = Challenge::decode(&code).or_else(|err| {
+            // Old codes hold just the word, so only a real word shows it was one
+            let old = Challenge::decode_legacy(&code).ok().filter(|challenge| {
+                language.fold_word(&challenge.word).is_ok_and(|word| is_guess(&word))
+            });
+            old.ok_or(CliError::BadCode(err))
+        })?;
+        if max_guesses.is_none() {
+            // Held to the same limits as --max-guesses, whatever made the code
+            let count = challenge.rules.max_guesses;
+            max_guesses = Some(in_range("--bword", count, MIN_GUESSES, MAX_GUESSES)?);
+        }
+        hard |= challenge.rules.hard_mode;
+        message = challenge.message;
+        word = Some(challenge.word);
+    }
+    let word = match word {
+        Some(word) => {
+            let folded = language.fold_word(&word).ok();
+            let folded = folded.filter(|word| is_guess(word));
+            let folded = folded.ok_or(CliError::NotAWord { word, language: language.name })?;
+            if let Some(len) = len.filter(|&len| len != folded.chars().count()) {
+                return Err(CliError::InvalidValue {
+                    option: "--length",
+                    value: len.to_string(),
+                    expected: format!("{}, the length of the word", folded.chars().count()),
+                });
+            }
+            if seed.is_some() {
+                return Err(CliError::Conflict("--seed", "a word"));
+            }
+            if boards.is_some() {
+                return Err(CliError::Conflict("--boards", "a word"));
+            }
+            if adversarial {
+                return Err(CliError::Conflict("--absurdle", "a word"));
+            }
+            if matches!(timing, Some((_, Timing::Speedrun, _))) {
+                return Err(CliError::Conflict("--speedrun", "a word"));
+            }
+            Some(folded)
+        }
+        None => None,
+    };
+    if adversarial {
+        if seed.is_some() {
+            return Err(CliError::Conflict("--absurdle", "--seed"));
+        } else if boards.is_some() {
+            return Err(CliError::Conflict("--absurdle", "--boards"));
+        }
+    }
+    if daily {
+        let other = if word.is_some() {
+            Some("a word")
+        } else if seed.is_some() {
+            Some("--seed")
+        } else if len.is_some() {
+            Some("--length")
+        } else if max_guesses.is_some() {
+            Some("--max-guesses")
+        } else if boards.is_some() {
+            Some("--boards")
+        } else if adversarial {
+            Some("--absurdle")
+        } else if let Some((option, _, _)) = timing {
+            Some(option)
+        } else {
+            answers.is_some().then_some("--answers")
+        };
+        if let Some(other) = other {
+            return Err(CliError::Conflict("--daily", other));
+        }
+    }
+
+    let share_url = share_url.or_else(|| Some((SHARE_URL_VAR, std::env::var(SHARE_URL_VAR).ok()?)));
+    let share_url = match share_url {
+        Some((option, url)) if !challenge::is_link_base(&url) => {
+            return Err(CliError::InvalidValue {
+                option,
+                value: url,
+                expected: "an http:// or https:// address".to_string(),
+            })
+        }
+        share_url => share_url.map(|(_, url)| url),
+    };
+
+    let args = Args {
+        word,
+        max_guesses,
+        hard_mode: hard.then_some(true),
+        answers,
+        guesses,
+        language: lang,
+        message,
+        link_error: None,
+        seed,
+        daily,
+        word_len: len,
+        boards,
+        adversarial,
+        timing: timing.map(|(_, mode, seconds)| (mode, seconds as u32)),
+        share_url,
+    };
+    Ok(Options { help: false, tui, args })
+}
+
+fn number<T: std::str::FromStr>(
+    option: &'static str,
+    value: String,
+    expected: &str,
+) -> Result<T, CliError> {
+    value.parse().map_err(|_| CliError::InvalidValue {
+        option,
+        value,
+        expected: expected.to_string(),
+    })
+}
+
+fn in_range(option: &'static str, n: usize, min: usize, max: usize) -> Result<usize, CliError> {
+    (min..=max).contains(&n).then_some(n).ok_or_else(|| CliError::InvalidValue {
+        option,
+        value: n.to_string(),
+        expected: format!("a number from {} to {}", min, max),
+    })
+}
+
+/// The custom word lists in the files at `answers` and `guesses`, unless they can't be read or
+/// used. The app reports why when it loads them itself.
+fn read_word_lists(
+    language: &Language,
+    answers: &str,
+    guesses: &Option<String>,
+) -> Option<WordList> {
+    let answers = std::fs::read_to_string(answers).ok()?;
+    let guesses = guesses.as_ref().map(std::fs::read_to_string).transpose().ok()?;
+    WordList::custom(language, &answers, guesses.as_deref()).ok()
+}
+
+fn is_word(language: &Language, word: &str) -> bool {
+    language.word_list(word.chars().count()).is_some_and(|words| words.is_guess(word))
+}
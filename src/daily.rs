@@ -0,0 +1,41 @@
+//! The daily puzzle: one answer per calendar day, the same for every player.
+//!
+//! The answer is derived from the puzzle number alone, so native and web clients agree without
+//! talking to a server.
+
+use chrono::{Duration, Local, NaiveDate};
+
+/// Mixed into every puzzle number so the answers don't simply walk through the word list.
+const DAILY_SEED: u64 = 0x5eed_d1ce_a5c1_1f00;
+
+/// The day of puzzle #1.
+fn first_day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2022, 1, 1).unwrap()
+}
+
+/// The puzzle number for `date`, counting from 1. Dates before the first puzzle get puzzle #1.
+pub fn puzzle_number(date: NaiveDate) -> u32 {
+    (date - first_day()).num_days().max(0) as u32 + 1
+}
+
+/// The puzzle number for today in the player's time zone.
+pub fn todays_puzzle() -> u32 {
+    puzzle_number(Local::now().date_naive())
+}
+
+/// Pick the answer for puzzle `number` from `list`.
+pub fn daily_word<S: AsRef<str>>(number: u32, list: &[S]) -> &str {
+    // SplitMix64, so that neighbouring days land far apart in the list
+    let mut x = DAILY_SEED.wrapping_add(u64::from(number).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    list[(x % list.len() as u64) as usize].as_ref()
+}
+
+/// How long until the next puzzle unlocks at local midnight.
+pub fn until_next_puzzle() -> Duration {
+    let now = Local::now().naive_local();
+    let midnight = (now.date() + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap();
+    midnight - now
+}
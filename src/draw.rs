@@ -1,124 +1,482 @@
-use crate::app::{GameState, WordleCell};
+use crate::app::{GameState, KeyboardKey, Palette, WordleCell};
+use crate::engine::{CellState, BOARDS_PER_ROW};
+use crate::keyboard::SpecialKeys;
+use crate::solver::Solver;
+use crate::stats::Stats;
+use crate::timer::format_time;
 use eframe::egui;
 use eframe::egui::style::Margin;
-use eframe::egui::{Color32, Response, RichText, Sense, Ui, Vec2};
+use eframe::egui::{Color32, Rect, Response, RichText, Sense, Stroke, Ui, Vec2};
+
+const CELL_SIZE: f32 = 48.0;
+const CELL_SPACING: f32 = 4.0;
+/// The gap between boards when there are several.
+const BOARD_SPACING: f32 = 16.0;
+/// The length of the longest bar in the guess distribution.
+const STATS_BAR_WIDTH: f32 = 240.0;
+/// Room left under the grid for the game end message and the keyboard.
+const BELOW_GRID_HEIGHT: f32 = 270.0;
+
+/// How far a rejected row moves either way when it shakes, and for how long.
+const SHAKE_DISTANCE: f32 = 8.0;
+const SHAKE_SECONDS: f64 = 0.4;
+/// How long each tile of a scored row takes to flip, and how long after the previous one it
+/// starts.
+const FLIP_SECONDS: f64 = 0.35;
+const FLIP_STAGGER: f64 = 0.25;
+/// How long a tile grows for when a letter is typed into it, and by how much.
+const POP_SECONDS: f64 = 0.1;
+const POP_SCALE: f32 = 0.12;
+/// How long each tile of a winning row jumps for, how far apart they start, and how high they go
+/// as a share of the tile size.
+const BOUNCE_SECONDS: f64 = 0.3;
+const BOUNCE_STAGGER: f64 = 0.1;
+const BOUNCE_HEIGHT: f32 = 0.3;
+
+/// When things last happened on the board, so they can be animated. Times are in seconds of
+/// egui's clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct BoardAnimation {
+    pub now: f64,
+    /// The row that was just scored, and when.
+    pub scored: Option<(usize, f64)>,
+    /// Whether that row won the game.
+    pub won: bool,
+    /// The row and column of the letter that was just typed, and when.
+    pub typed: Option<(usize, usize, f64)>,
+    /// The row that was just rejected, and when.
+    pub rejected: Option<(usize, f64)>,
+}
+
+/// How one tile looks at a moment of its animation.
+struct Tile {
+    state: CellState,
+    /// In points.
+    offset: Vec2,
+    scale: Vec2,
+}
+
+impl BoardAnimation {
+    /// How long the reveal of a row of `len` tiles takes, bounces included.
+    fn reveal_seconds(&self, len: usize) -> f64 {
+        let flips = FLIP_STAGGER * (len as f64 - 1.0) + FLIP_SECONDS;
+        if self.won {
+            flips + BOUNCE_STAGGER * (len as f64 - 1.0) + BOUNCE_SECONDS
+        } else {
+            flips
+        }
+    }
+
+    /// Whether anything on a board `len` tiles wide is still moving.
+    pub(crate) fn is_running(&self, len: usize) -> bool {
+        let running = |at: f64, seconds: f64| self.now - at < seconds;
+        self.scored.is_some_and(|(_, at)| running(at, self.reveal_seconds(len)))
+            || self.typed.is_some_and(|(_, _, at)| running(at, POP_SECONDS))
+            || self.rejected.is_some_and(|(_, at)| running(at, SHAKE_SECONDS))
+    }
+
+    fn tile(&self, row: usize, column: usize, len: usize, state: CellState, size: f32) -> Tile {
+        let mut tile = Tile { state, offset: Vec2::ZERO, scale: Vec2::splat(1.0) };
+        if let Some((scored_row, at)) = self.scored.filter(|&(scored_row, _)| scored_row == row) {
+            // Tiles turn over one after another, showing their color once they're edge on
+            let t = (self.now - at - FLIP_STAGGER * column as f64) / FLIP_SECONDS;
+            if t < 0.5 {
+                tile.state = CellState::Empty;
+            }
+            if (0.0..1.0).contains(&t) {
+                tile.scale.y = (t * std::f64::consts::PI).cos().abs() as f32;
+            }
+            let bounce_at = at
+                + FLIP_STAGGER * (len as f64 - 1.0)
+                + FLIP_SECONDS
+                + BOUNCE_STAGGER * column as f64;
+            let t = (self.now - bounce_at) / BOUNCE_SECONDS;
+            if self.won && scored_row == row && (0.0..1.0).contains(&t) {
+                tile.offset.y = -BOUNCE_HEIGHT * size * (t * std::f64::consts::PI).sin() as f32;
+            }
+        }
+        if let Some((_, _, at)) = self.typed.filter(|&(r, c, _)| (r, c) == (row, column)) {
+            let t = (self.now - at) / POP_SECONDS;
+            if (0.0..1.0).contains(&t) {
+                tile.scale *= 1.0 + POP_SCALE * (t * std::f64::consts::PI).sin() as f32;
+            }
+        }
+        if let Some((_, at)) = self.rejected.filter(|&(r, _)| r == row) {
+            tile.offset.x = shake_offset(self.now - at).unwrap_or(0.0);
+        }
+        tile
+    }
+}
+
+/// Draw the grid of each board, a few boards to a row, animated by `animations` unless it's
+/// `None`.
+pub(crate) fn draw_letters(
+    ui: &mut Ui,
+    boards: &[Vec<Vec<WordleCell>>],
+    palette: Palette,
+    animations: Option<&[BoardAnimation]>,
+) {
+    // Shrink the cells if a long word, a tall board or many boards don't fit at full size, then
+    // center the grids
+    let columns = boards.first().and_then(|board| board.first()).map_or(0, Vec::len) as f32;
+    let rows = boards.first().map_or(0, Vec::len) as f32;
+    let across = boards.len().clamp(1, BOARDS_PER_ROW) as f32;
+    let down = boards.len().div_ceil(BOARDS_PER_ROW).max(1) as f32;
+    let spacing = CELL_SPACING * (columns - 1.0).max(0.0);
+    let board_space = (ui.available_width() - BOARD_SPACING * (across - 1.0)) / across;
+    let fit_width = (board_space - spacing - 2.0 * SHAKE_DISTANCE) / columns;
+    let fit_height = (ui.available_height() - BELOW_GRID_HEIGHT - BOARD_SPACING * (down - 1.0))
+        / (rows * down)
+        - CELL_SPACING;
+    let cell_size = CELL_SIZE.min(fit_width).min(fit_height).max(16.0);
+    // Room either side for a row to shake into
+    let board_width = cell_size * columns + spacing + 2.0 * SHAKE_DISTANCE;
+    let grid_width = board_width * across + BOARD_SPACING * (across - 1.0);
 
-pub(crate) fn draw_letters(ui: &mut Ui, cells: &[[WordleCell; 5]; 6]) {
     #[rustfmt::skip] // rustfmt wants to put none(), .margin() and .show() all on one line
     egui::Frame::none()
-        .margin(Margin::symmetric(ui.available_width() / 2.0 - 128.0, 12.0))
+        .margin(Margin::symmetric(((ui.available_width() - grid_width) / 2.0).max(0.0), 12.0))
         .show(ui, |ui| {
-            egui::Grid::new("wordle_grid").spacing((4.0, 4.0)).show(ui, |ui| {
-                for row in cells {
-                    for cell in row {
-                        // Draw a cell
-                        egui::Frame::none()
-                            .fill(cell.state.get_color(false))
-                            .rounding(6.0)
-                            .show(ui, |ui| {
-                                add_letter_label(
-                                    ui,
-                                    (48.0, 48.0),
-                                    &cell.letter.to_string(),
-                                    36.0,
-                                    false,
-                                );
-                            });
-                    }
-                    ui.end_row();
+            ui.spacing_mut().item_spacing = (CELL_SPACING, CELL_SPACING).into();
+            for (i, row) in boards.chunks(BOARDS_PER_ROW).enumerate() {
+                if i > 0 {
+                    ui.add_space(BOARD_SPACING - CELL_SPACING);
                 }
-            });
+                ui.horizontal(|ui| {
+                    for (j, board) in row.iter().enumerate() {
+                        if j > 0 {
+                            ui.add_space(BOARD_SPACING - CELL_SPACING);
+                        }
+                        let animation = animations.map(|animations| {
+                            &animations[i * BOARDS_PER_ROW + j]
+                        });
+                        ui.vertical(|ui| draw_board(ui, board, cell_size, palette, animation));
+                    }
+                });
+            }
         });
 }
 
+fn draw_board(
+    ui: &mut Ui,
+    cells: &[Vec<WordleCell>],
+    cell_size: f32,
+    palette: Palette,
+    animation: Option<&BoardAnimation>,
+) {
+    for (i, row) in cells.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add_space(SHAKE_DISTANCE);
+            for (j, cell) in row.iter().enumerate() {
+                let tile = match animation {
+                    Some(animation) => animation.tile(i, j, row.len(), cell.state, cell_size),
+                    None => Tile { state: cell.state, offset: Vec2::ZERO, scale: Vec2::splat(1.0) },
+                };
+                draw_tile(ui, cell.letter, &tile, cell_size, palette);
+            }
+            ui.add_space(SHAKE_DISTANCE - CELL_SPACING);
+        });
+    }
+}
+
+/// Draw one tile of the grid. Tiles are painted rather than laid out, so they can move and
+/// change size without pushing their neighbours around.
+fn draw_tile(ui: &mut Ui, letter: char, tile: &Tile, size: f32, palette: Palette) {
+    let (space, _) = ui.allocate_exact_size(Vec2::splat(size), Sense::hover());
+    let rect = Rect::from_center_size(space.center() + tile.offset, tile.scale * size);
+    let text_color = tile.state.get_text_color(palette);
+    ui.painter().rect_filled(rect, 6.0, tile.state.get_color(false, palette));
+    ui.painter().text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        letter,
+        egui::FontId::proportional(size * 0.75 * tile.scale.y.max(0.01)),
+        text_color,
+    );
+    if palette.symbols {
+        paint_symbol(ui, rect, tile.state, text_color);
+    }
+}
+
+/// How far a rejected row has moved `seconds` after it was rejected: a few quick swings that die
+/// away, or `None` once it has stopped.
+pub(crate) fn shake_offset(seconds: f64) -> Option<f32> {
+    (0.0..SHAKE_SECONDS).contains(&seconds).then(|| {
+        let swing = (seconds * 5.0 * std::f64::consts::TAU).sin() * (1.0 - seconds / SHAKE_SECONDS);
+        SHAKE_DISTANCE * swing as f32
+    })
+}
+
+/// Show a short message over the top of the board.
+pub(crate) fn draw_toast(ctx: &egui::Context, text: &str, palette: Palette) {
+    let (fill, color) = if palette.dark_mode {
+        (Color32::WHITE, Color32::BLACK)
+    } else {
+        (Color32::BLACK, Color32::WHITE)
+    };
+    egui::Area::new("toast").anchor(egui::Align2::CENTER_TOP, (0.0, 64.0)).show(ctx, |ui| {
+        egui::Frame::none()
+            .fill(fill)
+            .rounding(6.0)
+            .margin(Margin::symmetric(16.0, 10.0))
+            .show(ui, |ui| ui.label(RichText::new(text).size(18.0).strong().color(color)));
+    });
+}
+
+/// The width of a letter key, and of the wider ENT and DEL keys.
+const KEY_WIDTH: f32 = 36.0;
+const BIG_KEY_WIDTH: f32 = 56.0;
+const KEY_SPACING: f32 = 4.0;
+
+/// Draw the keyboard rows centered under each other, with ENT and DEL where `special_keys` says.
 pub(crate) fn draw_keyboard(
     ui: &mut Ui,
-    keyboard: &([WordleCell; 10], [WordleCell; 9], [WordleCell; 7]),
+    keyboard: &[Vec<KeyboardKey>],
+    special_keys: SpecialKeys,
+    palette: Palette,
     kbd_keydown: &mut String,
 ) {
+    let (left, right) = match special_keys {
+        SpecialKeys::EnterLeft => (Some("ENT"), Some("DEL")),
+        SpecialKeys::EnterRight => (Some("DEL"), Some("ENT")),
+        SpecialKeys::Below => (None, None),
+    };
+    for (i, row) in keyboard.iter().enumerate() {
+        if i + 1 == keyboard.len() {
+            add_keyboard_line(ui, left, row, right, palette, kbd_keydown);
+        } else {
+            add_keyboard_line(ui, None, row, None, palette, kbd_keydown);
+        }
+    }
+    if special_keys == SpecialKeys::Below {
+        add_keyboard_line(ui, Some("ENT"), &[], Some("DEL"), palette, kbd_keydown);
+    }
+}
+
+/// Add one centered row of letter keys, with an optional big key either side.
+fn add_keyboard_line(
+    ui: &mut Ui,
+    left: Option<&str>,
+    row: &[KeyboardKey],
+    right: Option<&str>,
+    palette: Palette,
+    kbd_keydown: &mut String,
+) {
+    let big_keys = left.iter().chain(&right).count() as f32;
+    let keys = row.len() as f32 + big_keys;
+    let width =
+        KEY_WIDTH * row.len() as f32 + BIG_KEY_WIDTH * big_keys + KEY_SPACING * (keys - 1.0);
     #[rustfmt::skip] // rustfmt wants to put none(), .margin() and .show() all on one line
     egui::Frame::none()
-        .margin(Margin::symmetric(ui.available_width() / 2.0 - 198.0, 0.0))
+        .margin(Margin::symmetric(((ui.available_width() - width) / 2.0).max(0.0), 0.0))
         .show(ui, |ui| {
-            // Add first row
             ui.horizontal(|ui| {
-                ui.spacing_mut().item_spacing = (4.0, 0.0).into();
-                add_keyboard_row(ui, &keyboard.0, kbd_keydown);
-            });
-
-            // Add second row
-            ui.horizontal(|ui| {
-                ui.spacing_mut().item_spacing = (4.0, 0.0).into();
-                egui::Frame::none().margin(Margin::symmetric(20.0, 0.0)).show(
-                    ui,
-                    |ui| {
-                        add_keyboard_row(ui, &keyboard.1, kbd_keydown);
-                    },
-                );
-            });
-
-            // Add third row
-            ui.horizontal(|ui| {
-                ui.spacing_mut().item_spacing = (4.0, 0.0).into();
-                add_keyboard_button(ui, "ENT", true, Color32::GRAY, kbd_keydown);
-                add_keyboard_row(ui, &keyboard.2, kbd_keydown);
-                add_keyboard_button(ui, "DEL", true, Color32::GRAY, kbd_keydown);
+                ui.spacing_mut().item_spacing = (KEY_SPACING, 0.0).into();
+                if let Some(text) = left {
+                    add_keyboard_button(ui, text, true, &[CellState::Empty], palette, kbd_keydown);
+                }
+                add_keyboard_row(ui, row, palette, kbd_keydown);
+                if let Some(text) = right {
+                    add_keyboard_button(ui, text, true, &[CellState::Empty], palette, kbd_keydown);
+                }
             });
         });
 }
 
-fn add_keyboard_row(ui: &mut Ui, row: &[WordleCell], kbd_keydown: &mut String) {
+fn add_keyboard_row(ui: &mut Ui, row: &[KeyboardKey], palette: Palette, kbd_keydown: &mut String) {
     for key in row {
-        add_keyboard_button(
-            ui,
-            &key.letter.to_string(),
-            false,
-            key.state.get_color(true),
-            kbd_keydown,
-        )
+        add_keyboard_button(ui, &key.letter.to_string(), false, &key.states, palette, kbd_keydown)
     }
 }
 
+/// Add a key colored by the state of its letter on each board. With several boards the key is
+/// split into a part for each, laid out like the boards.
 fn add_keyboard_button(
     ui: &mut Ui,
     text: &str,
     big: bool,
-    color: Color32,
+    states: &[CellState],
+    palette: Palette,
     kbd_keydown: &mut String,
 ) {
-    #[rustfmt::skip] // rustfmt wants to format 'if ui.add_sized' weird
-    egui::Frame::none().fill(color).rounding(6.0).show(ui, |ui| {
-        if add_letter_label(
-            ui,
-            (if big { 56.0 } else { 36.0 }, 48.0),
-            text,
-            18.0,
-            true,
-        ).clicked() && kbd_keydown.is_empty() {
-            *kbd_keydown = text.to_string();
+    // The letter is white as soon as any part of the key is colored
+    let text_state =
+        states.iter().copied().find(|&state| state != CellState::Empty).unwrap_or(CellState::Empty);
+    let text_color = text_state.get_text_color(palette);
+    // Reserve a place behind the letter to paint the key into once its size is known
+    let background = ui.painter().add(egui::Shape::Noop);
+    let response = add_letter_label(
+        ui,
+        (if big { BIG_KEY_WIDTH } else { KEY_WIDTH }, 48.0),
+        text,
+        18.0,
+        text_color,
+        true,
+    );
+    if response.clicked() && kbd_keydown.is_empty() {
+        *kbd_keydown = text.to_string();
+    }
+
+    let rect = response.rect;
+    let across = states.len().clamp(1, BOARDS_PER_ROW);
+    let down = states.len().div_ceil(BOARDS_PER_ROW);
+    let part_size = Vec2::new(rect.width() / across as f32, rect.height() / down.max(1) as f32);
+    let mut parts = Vec::new();
+    for (i, &state) in states.iter().enumerate() {
+        let (column, row) = (i % across, i / across);
+        let min = rect.min + Vec2::new(column as f32, row as f32) * part_size;
+        let part = Rect::from_min_size(min, part_size);
+        // Only the corners of the whole key are rounded
+        let corner = |at_column, at_row| if at_column && at_row { 6.0 } else { 0.0 };
+        let rounding = egui::Rounding {
+            nw: corner(column == 0, row == 0),
+            ne: corner(column + 1 == across, row == 0),
+            sw: corner(column == 0, row + 1 == down),
+            se: corner(column + 1 == across, row + 1 == down),
+        };
+        parts.push(egui::Shape::rect_filled(part, rounding, state.get_color(true, palette)));
+        if palette.symbols {
+            paint_symbol(ui, part, state, state.get_text_color(palette));
         }
-    });
+    }
+    ui.painter().set(background, egui::Shape::Vec(parts));
+}
+
+/// Mark a scored cell with a shape as well as a color, for players who can't rely on color: a
+/// dot for a letter in the right place, and a ring for one in the wrong place.
+fn paint_symbol(ui: &Ui, rect: Rect, state: CellState, color: Color32) {
+    let radius = rect.width().min(rect.height()) * 0.09;
+    let center = rect.right_top() + Vec2::new(-2.0 * radius, 2.0 * radius);
+    match state {
+        CellState::Green => ui.painter().circle_filled(center, radius, color),
+        CellState::Yellow => {
+            ui.painter().circle_stroke(center, radius, Stroke::new(radius * 0.5, color))
+        }
+        _ => {}
+    }
 }
 
-pub(crate) fn draw_game_end_message(ui: &mut Ui, game_state: &GameState, word: &str) {
+pub(crate) fn draw_game_end_message(ui: &mut Ui, game_state: &GameState, answers: &[&str]) {
+    let label = game_state.get_state_label(answers);
     #[rustfmt::skip] // rustfmt wants to put none(), .margin() and .show() all on one line
     egui::Frame::none()
         .margin(Margin { left: 0.0, right: 0.0, top: 12.0, bottom: 24.0 })
         .show(ui, |ui| {
-            ui.add_sized((400.0, 30.0), game_state.get_state_label(word));
+            ui.add_sized((400.0, 30.0), label);
         });
 }
 
+/// Show which daily puzzle is being played and, once it's over, when the next one unlocks.
+/// Returns true if the player asked to start the next puzzle.
+pub(crate) fn draw_daily_status(ui: &mut Ui, number: u32, game_over: bool) -> bool {
+    ui.label(RichText::new(format!("Daily #{}", number)).size(18.0).strong());
+    if !game_over {
+        return false;
+    }
+    if crate::daily::todays_puzzle() > number {
+        return ui.button("Play today's puzzle").clicked();
+    }
+    let left = crate::daily::until_next_puzzle();
+    ui.label(format!(
+        "Next puzzle in {:02}:{:02}:{:02}",
+        left.num_hours(),
+        left.num_minutes() % 60,
+        left.num_seconds() % 60,
+    ));
+    // Keep the countdown ticking
+    ui.ctx().request_repaint();
+    false
+}
+
+/// Show the summary numbers and a histogram of how many guesses each win took. The bar for
+/// `highlight` guesses is drawn in the color of a correct letter, to mark the game that was just
+/// won.
+pub(crate) fn draw_stats(ui: &mut Ui, stats: &Stats, highlight: Option<usize>, palette: Palette) {
+    ui.horizontal(|ui| {
+        for (value, caption) in [
+            (stats.played, "Played"),
+            (stats.win_percentage(), "Win %"),
+            (stats.current_streak, "Current streak"),
+            (stats.max_streak, "Max streak"),
+        ] {
+            ui.vertical_centered(|ui| {
+                ui.set_width(72.0);
+                ui.label(RichText::new(value.to_string()).size(28.0).strong());
+                ui.label(caption);
+            });
+        }
+    });
+
+    ui.add_space(12.0);
+    ui.label(RichText::new("Guess distribution").strong());
+    let most = stats.distribution.iter().copied().max().unwrap_or(0).max(1) as f32;
+    for (i, &count) in stats.distribution.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add_sized((16.0, 20.0), egui::Label::new((i + 1).to_string()));
+            let state = if highlight == Some(i + 1) { CellState::Green } else { CellState::Gray };
+            egui::Frame::none().fill(state.get_color(false, palette)).show(ui, |ui| {
+                ui.add_sized(
+                    (24.0 + STATS_BAR_WIDTH * count as f32 / most, 20.0),
+                    egui::Label::new(RichText::new(count.to_string()).color(Color32::WHITE)),
+                );
+            });
+        });
+    }
+    if stats.distribution.is_empty() {
+        ui.label("No wins yet");
+    }
+
+    if let Some(fastest) = stats.fastest_win {
+        ui.add_space(8.0);
+        ui.label(format!("Fastest win: {}", format_time(fastest.as_secs_f64())));
+    }
+    if stats.best_speedrun > 0 {
+        ui.label(format!("Best speedrun: {} words", stats.best_speedrun));
+    }
+}
+
+/// Show how many answers are still possible and the solver's best next guesses.
+pub(crate) fn draw_assistant(ui: &mut Ui, solver: &Solver, palette: Palette) {
+    ui.heading("Assistant");
+    let candidates = solver.candidates();
+    ui.label(match candidates.len() {
+        1 => "1 possible answer".to_string(),
+        n => format!("{} possible answers", n),
+    });
+    if candidates.len() <= 10 {
+        ui.label(candidates.join(", "));
+    }
+
+    ui.add_space(8.0);
+    ui.label(RichText::new("Best guesses").strong());
+    if !solver.is_done() {
+        ui.add(egui::ProgressBar::new(solver.progress()).desired_width(160.0));
+    }
+    egui::Grid::new("assistant_grid").striped(true).show(ui, |ui| {
+        for suggestion in solver.suggestions(5) {
+            let word = RichText::new(&suggestion.word).monospace().strong();
+            ui.label(if suggestion.possible {
+                word.color(CellState::Green.get_color(false, palette))
+            } else {
+                word
+            });
+            ui.label(format!("{:.2} bits", suggestion.entropy));
+            ui.end_row();
+        }
+    });
+    ui.small("Highlighted words could be the answer.");
+}
+
 fn add_letter_label(
     ui: &mut Ui,
     size: impl Into<Vec2>,
     text: &str,
     text_size: f32,
+    color: Color32,
     clickable: bool,
 ) -> Response {
     ui.add_sized(size, {
-        let label =
-            egui::Label::new(RichText::new(text).size(text_size).strong().color(Color32::WHITE));
+        let label = egui::Label::new(RichText::new(text).size(text_size).strong().color(color));
         if clickable {
             label.sense(Sense::click())
         } else {
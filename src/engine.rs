@@ -0,0 +1,611 @@
+//! The rules of the game, with no dependency on any front-end.
+//!
+//! A [`Game`] holds the answer and every guess made so far. Front-ends feed it guesses and read
+//! back the scored [`history`](Game::history) and [`state`](Game::state); they never score words
+//! themselves. A [`MultiGame`] plays the same guesses on several boards at once, and an
+//! [`adversarial`](Game::adversarial) game puts off choosing its answer for as long as it can.
+
+use crate::words::WordList;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// The number of guesses a player gets before the game is lost, unless the rules say otherwise.
+pub const DEFAULT_MAX_GUESSES: usize = 6;
+/// The fewest and most guesses a game can be set up with.
+pub const MIN_GUESSES: usize = 2;
+pub const MAX_GUESSES: usize = 12;
+/// How many boards a game can be played on at once.
+pub const BOARD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+/// How many boards are shown side by side before another row of them is started.
+pub const BOARDS_PER_ROW: usize = 4;
+
+/// The guess limit for a game on `boards` boards: one more guess for every extra board.
+pub fn scaled_max_guesses(max_guesses: usize, boards: usize) -> usize {
+    max_guesses + boards.saturating_sub(1)
+}
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellState {
+    Empty,
+    Gray,
+    Yellow,
+    Green,
+}
+
+impl CellState {
+    /// Raise this state to `state` if `state` carries more information, as a keyboard key does
+    /// when the same letter is scored several times.
+    pub fn promote(&mut self, state: CellState) {
+        match state {
+            Self::Empty => {}
+            Self::Gray => {
+                if matches!(self, Self::Empty) {
+                    *self = state;
+                }
+            }
+            Self::Yellow => {
+                if matches!(self, Self::Empty | Self::Gray) {
+                    *self = state;
+                }
+            }
+            Self::Green => {
+                *self = state;
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameState {
+    Playing,
+    Success(usize),
+    Failure,
+}
+
+/// The colors of a scored word, packed into a single value so that patterns can be compared and
+/// counted cheaply. Each letter is one base-3 digit (gray 0, yellow 1, green 2), with the first
+/// letter in the lowest digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pattern {
+    code: u32,
+    len: usize,
+}
+
+impl Pattern {
+    /// Pack a row of states. `Empty` cells are treated as gray.
+    pub fn from_states(states: &[CellState]) -> Self {
+        let code = states.iter().rev().fold(0, |code, state| {
+            code * 3
+                + match state {
+                    CellState::Empty | CellState::Gray => 0,
+                    CellState::Yellow => 1,
+                    CellState::Green => 2,
+                }
+        });
+        Self { code, len: states.len() }
+    }
+
+    /// The pattern of a correct guess.
+    pub fn solved(len: usize) -> Self {
+        Self::from_states(&vec![CellState::Green; len])
+    }
+
+    pub fn code(&self) -> u32 {
+        self.code
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_solved(&self) -> bool {
+        *self == Self::solved(self.len)
+    }
+
+    pub fn states(&self) -> Vec<CellState> {
+        let mut code = self.code;
+        (0..self.len)
+            .map(|_| {
+                let state = match code % 3 {
+                    0 => CellState::Gray,
+                    1 => CellState::Yellow,
+                    _ => CellState::Green,
+                };
+                code /= 3;
+                state
+            })
+            .collect()
+    }
+}
+
+/// A word that has been accepted and scored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guess {
+    pub word: String,
+    pub pattern: Pattern,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    GameOver,
+    WrongLength {
+        expected: usize,
+        found: usize,
+    },
+    NotInWordList,
+    /// Hard mode is on and the guess leaves out a revealed green. `position` counts from zero.
+    MissingGreen {
+        letter: char,
+        position: usize,
+    },
+    /// Hard mode is on and the guess has fewer copies of a revealed letter than earlier hints
+    /// showed the answer has.
+    MissingLetter {
+        letter: char,
+        count: usize,
+    },
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GameOver => write!(f, "The game is already over"),
+            Self::WrongLength { expected, found } if found < expected => {
+                write!(f, "Not enough letters")
+            }
+            Self::WrongLength { .. } => write!(f, "Too many letters"),
+            Self::NotInWordList => write!(f, "Not in word list"),
+            Self::MissingGreen { letter, position } => {
+                write!(f, "Letter {} must be {}", position + 1, letter)
+            }
+            Self::MissingLetter { letter, count: 1 } => write!(f, "Guess must contain {}", letter),
+            Self::MissingLetter { letter, count } => {
+                write!(f, "Guess must contain {} {}s", count, letter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// Settings that change how a game is played, fixed when the game starts.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    pub max_guesses: usize,
+    /// Every guess must reuse the hints revealed so far: greens in place, and revealed letters
+    /// somewhere in the word.
+    pub hard_mode: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self { max_guesses: DEFAULT_MAX_GUESSES, hard_mode: false }
+    }
+}
+
+/// Everything needed to rebuild a game. Only the words are kept: the colors are worked out again
+/// by replaying them, so a saved game can never disagree with the rules.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SavedGame {
+    pub answer: String,
+    pub rules: Rules,
+    pub guesses: Vec<String>,
+    /// The answers of any other boards.
+    #[cfg_attr(feature = "persistence", serde(default))]
+    pub others: Vec<String>,
+    /// The game was adversarial, so `answer` is only whatever it had settled on so far.
+    #[cfg_attr(feature = "persistence", serde(default))]
+    pub adversarial: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    answer: String,
+    /// The words that could still be the answer of an adversarial game. `answer` is one of them.
+    candidates: Option<Vec<String>>,
+    rules: Rules,
+    words: Arc<WordList>,
+    history: Vec<Guess>,
+    state: GameState,
+}
+
+impl Game {
+    pub fn new(answer: &str) -> Self {
+        Self::with_rules(answer, Rules::default())
+    }
+
+    /// A game that accepts the built-in guesses for the answer's length.
+    pub fn with_rules(answer: &str, rules: Rules) -> Self {
+        let len = answer.chars().count();
+        let words = WordList::builtin(len).unwrap_or_else(|| WordList::empty(len));
+        Self::with_words(answer, rules, Arc::new(words))
+    }
+
+    /// A game that accepts the guesses in `words`. The answer doesn't have to be one of them.
+    pub fn with_words(answer: &str, rules: Rules, words: Arc<WordList>) -> Self {
+        Self {
+            answer: answer.to_uppercase(),
+            candidates: None,
+            rules,
+            words,
+            history: Vec::new(),
+            state: GameState::Playing,
+        }
+    }
+
+    /// A game with no answer chosen yet: any of the answers in `words` could be it. Each guess is
+    /// scored in whichever way leaves the most of them, so the answer is only settled when
+    /// there's no other choice.
+    pub fn adversarial(rules: Rules, words: Arc<WordList>) -> Self {
+        let candidates: Vec<String> = words.answers().iter().map(|word| word.to_string()).collect();
+        let answer = candidates.first().cloned().unwrap_or_else(|| " ".repeat(words.word_len()));
+        Self { candidates: Some(candidates), ..Self::with_words(&answer, rules, words) }
+    }
+
+    /// The answer, or for an adversarial game one of the words that could still be.
+    pub fn answer(&self) -> &str {
+        &self.answer
+    }
+
+    pub fn is_adversarial(&self) -> bool {
+        self.candidates.is_some()
+    }
+
+    /// The words an adversarial game could still settle on.
+    pub fn candidates(&self) -> Option<&[String]> {
+        self.candidates.as_deref()
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn words(&self) -> &Arc<WordList> {
+        &self.words
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.rules.max_guesses
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.answer.chars().count()
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    pub fn is_over(&self) -> bool {
+        !matches!(self.state, GameState::Playing)
+    }
+
+    pub fn history(&self) -> &[Guess] {
+        &self.history
+    }
+
+    /// The best state each guessed letter has reached so far. Letters that have not been guessed
+    /// are missing from the map.
+    pub fn letter_states(&self) -> HashMap<char, CellState> {
+        let mut states = HashMap::new();
+        for guess in &self.history {
+            for (letter, state) in guess.word.chars().zip(guess.pattern.states()) {
+                states.entry(letter).or_insert(CellState::Empty).promote(state);
+            }
+        }
+        states
+    }
+
+    pub fn save(&self) -> SavedGame {
+        SavedGame {
+            answer: self.answer.clone(),
+            rules: self.rules.clone(),
+            guesses: self.history.iter().map(|guess| guess.word.clone()).collect(),
+            others: Vec::new(),
+            adversarial: self.is_adversarial(),
+        }
+    }
+
+    /// Rebuild a saved game by playing its guesses again. Fails if any of them is no longer
+    /// accepted, for example because the word list has changed.
+    pub fn restore(saved: &SavedGame) -> Result<Self, GuessError> {
+        let len = saved.answer.chars().count();
+        let words = WordList::builtin(len).unwrap_or_else(|| WordList::empty(len));
+        Self::restore_with_words(saved, Arc::new(words))
+    }
+
+    /// Like [`restore`](Self::restore), checking the guesses against `words`.
+    pub fn restore_with_words(saved: &SavedGame, words: Arc<WordList>) -> Result<Self, GuessError> {
+        let mut game = if saved.adversarial {
+            Self::adversarial(saved.rules.clone(), words)
+        } else {
+            Self::with_words(&saved.answer, saved.rules.clone(), words)
+        };
+        for word in &saved.guesses {
+            game.guess(word)?;
+        }
+        Ok(game)
+    }
+
+    /// Check that `word` would be accepted as the next guess, without making it. Returns the word
+    /// as it would be recorded.
+    ///
+    /// Only the case of `word` is changed, so anything typed in a language with accents has to be
+    /// folded with [`Language::fold_word`](crate::language::Language::fold_word) first.
+    pub fn check(&self, word: &str) -> Result<String, GuessError> {
+        if self.is_over() {
+            return Err(GuessError::GameOver);
+        }
+        let word = word.to_uppercase();
+        let found = word.chars().count();
+        if found != self.word_len() {
+            return Err(GuessError::WrongLength { expected: self.word_len(), found });
+        }
+        if !self.words.is_guess(&word) {
+            return Err(GuessError::NotInWordList);
+        }
+        if self.rules.hard_mode {
+            check_hard_mode(&word, &self.history)?;
+        }
+        Ok(word)
+    }
+
+    /// Give up, losing the game if it's still being played, as when time runs out.
+    pub fn resign(&mut self) {
+        if !self.is_over() {
+            self.state = GameState::Failure;
+        }
+    }
+
+    /// Score `word` against the answer and record it, ending the game if it was the last guess.
+    /// An adversarial game scores it against its candidates instead, and keeps those that fit.
+    pub fn guess(&mut self, word: &str) -> Result<&Guess, GuessError> {
+        let word = self.check(word)?;
+        let pattern = match &mut self.candidates {
+            Some(candidates) => {
+                let pattern = hardest_pattern(&word, candidates);
+                candidates.retain(|candidate| score_guess(&word, candidate) == pattern);
+                if let Some(answer) = candidates.first() {
+                    self.answer = answer.clone();
+                }
+                pattern
+            }
+            None => score_guess(&word, &self.answer),
+        };
+        if pattern.is_solved() {
+            self.state = GameState::Success(self.history.len() + 1);
+        } else if self.history.len() + 1 >= self.rules.max_guesses {
+            self.state = GameState::Failure;
+        }
+        self.history.push(Guess { word, pattern });
+        Ok(self.history.last().unwrap())
+    }
+}
+
+/// Several games played with the same guesses, one board per answer. A board locks once it's
+/// solved, and the game is won when every board is.
+#[derive(Debug, Clone)]
+pub struct MultiGame {
+    boards: Vec<Game>,
+    state: GameState,
+}
+
+impl From<Game> for MultiGame {
+    fn from(game: Game) -> Self {
+        let state = game.state().clone();
+        Self { boards: vec![game], state }
+    }
+}
+
+impl MultiGame {
+    /// A game with a board for each of `answers`, which must all be the same length. The rules
+    /// are for the whole game, so the guess limit should already allow for the extra boards.
+    pub fn new<S: AsRef<str>>(answers: &[S], rules: Rules, words: Arc<WordList>) -> Self {
+        let boards = answers
+            .iter()
+            .map(|answer| Game::with_words(answer.as_ref(), rules.clone(), words.clone()))
+            .collect();
+        Self { boards, state: GameState::Playing }
+    }
+
+    pub fn boards(&self) -> &[Game] {
+        &self.boards
+    }
+
+    pub fn answers(&self) -> Vec<&str> {
+        self.boards.iter().map(Game::answer).collect()
+    }
+
+    pub fn rules(&self) -> &Rules {
+        self.boards[0].rules()
+    }
+
+    pub fn words(&self) -> &Arc<WordList> {
+        self.boards[0].words()
+    }
+
+    pub fn max_guesses(&self) -> usize {
+        self.rules().max_guesses
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.boards[0].word_len()
+    }
+
+    /// `Success` holds the number of guesses it took to solve the last board.
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    pub fn is_over(&self) -> bool {
+        !matches!(self.state, GameState::Playing)
+    }
+
+    /// Every word guessed so far, in order. Boards that were solved early have only some of them.
+    pub fn guesses(&self) -> Vec<&str> {
+        let longest = self.boards.iter().map(Game::history).max_by_key(|history| history.len());
+        longest.unwrap_or_default().iter().map(|guess| guess.word.as_str()).collect()
+    }
+
+    /// The best state each guessed letter has reached on each board.
+    pub fn letter_states(&self) -> Vec<HashMap<char, CellState>> {
+        self.boards.iter().map(Game::letter_states).collect()
+    }
+
+    pub fn save(&self) -> SavedGame {
+        SavedGame {
+            answer: self.boards[0].answer().to_string(),
+            rules: self.rules().clone(),
+            guesses: self.guesses().into_iter().map(String::from).collect(),
+            others: self.boards[1..].iter().map(|board| board.answer().to_string()).collect(),
+            adversarial: self.boards[0].is_adversarial(),
+        }
+    }
+
+    /// Rebuild a saved game by playing its guesses again on every board, checking them against
+    /// `words`.
+    pub fn restore_with_words(saved: &SavedGame, words: Arc<WordList>) -> Result<Self, GuessError> {
+        if saved.adversarial {
+            return Game::restore_with_words(saved, words).map(Self::from);
+        }
+        let answers: Vec<&str> =
+            std::iter::once(&saved.answer).chain(&saved.others).map(String::as_str).collect();
+        let mut game = Self::new(&answers, saved.rules.clone(), words);
+        for word in &saved.guesses {
+            game.guess(word)?;
+        }
+        Ok(game)
+    }
+
+    /// Give up on every board that isn't solved yet.
+    pub fn resign(&mut self) {
+        if !self.is_over() {
+            self.boards.iter_mut().for_each(Game::resign);
+            self.state = GameState::Failure;
+        }
+    }
+
+    /// Score `word` on every board that isn't solved yet. It's only accepted if every one of them
+    /// accepts it, so in hard mode it must use the hints of each.
+    pub fn guess(&mut self, word: &str) -> Result<(), GuessError> {
+        if self.is_over() {
+            return Err(GuessError::GameOver);
+        }
+        let playing = || self.boards.iter().filter(|board| !board.is_over());
+        for board in playing() {
+            board.check(word)?;
+        }
+        for board in self.boards.iter_mut().filter(|board| !board.is_over()) {
+            board.guess(word)?;
+        }
+        let solved = self.boards.iter().all(|board| matches!(board.state(), GameState::Success(_)));
+        if solved {
+            self.state = GameState::Success(self.guesses().len());
+        } else if self.boards.iter().any(|board| matches!(board.state(), GameState::Failure)) {
+            self.state = GameState::Failure;
+        }
+        Ok(())
+    }
+}
+
+/// Color each letter of `guess` against `answer`. Both words must be the same length.
+///
+/// Greens are assigned first. Each remaining letter of the guess, from left to right, is then
+/// yellow only while the answer still has an unmatched copy of that letter, so a repeated letter
+/// is never colored more times than it appears in the answer.
+pub fn score_guess(guess: &str, answer: &str) -> Pattern {
+    let guess: Vec<char> = guess.chars().collect();
+    let answer: Vec<char> = answer.chars().collect();
+    let mut states = vec![CellState::Gray; guess.len()];
+
+    // First pass: exact matches, counting the answer letters they don't use up
+    let mut unmatched: HashMap<char, usize> = HashMap::new();
+    for (i, (&g, &a)) in guess.iter().zip(&answer).enumerate() {
+        if g == a {
+            states[i] = CellState::Green;
+        } else {
+            *unmatched.entry(a).or_insert(0) += 1;
+        }
+    }
+
+    // Second pass: misplaced letters, limited by what the answer has left over
+    for (i, g) in guess.iter().enumerate() {
+        if matches!(states[i], CellState::Green) {
+            continue;
+        }
+        if let Some(count) = unmatched.get_mut(g).filter(|count| **count > 0) {
+            *count -= 1;
+            states[i] = CellState::Yellow;
+        }
+    }
+
+    Pattern::from_states(&states)
+}
+
+/// The pattern that `guess` gets against the most of `candidates`, which is how an adversarial
+/// game scores it. Ties go to the pattern that reveals least, so a correct guess is only scored
+/// as one when it's the last candidate left.
+pub fn hardest_pattern<S: AsRef<str>>(guess: &str, candidates: &[S]) -> Pattern {
+    let mut counts: HashMap<Pattern, usize> = HashMap::new();
+    for candidate in candidates {
+        *counts.entry(score_guess(guess, candidate.as_ref())).or_insert(0) += 1;
+    }
+    let len = guess.chars().count();
+    counts
+        .into_iter()
+        .max_by_key(|&(pattern, count)| {
+            (count, !pattern.is_solved(), std::cmp::Reverse(pattern.code()))
+        })
+        .map_or(Pattern::solved(len), |(pattern, _)| pattern)
+}
+
+/// Check that `word` uses every hint revealed by `history`. Greens are checked first, from left to
+/// right, so the first error reported is the one a player would notice first.
+pub fn check_hard_mode(word: &str, history: &[Guess]) -> Result<(), GuessError> {
+    let word: Vec<char> = word.chars().collect();
+    let mut required: HashMap<char, usize> = HashMap::new();
+
+    for guess in history {
+        let mut revealed: HashMap<char, usize> = HashMap::new();
+        for (position, (letter, state)) in
+            guess.word.chars().zip(guess.pattern.states()).enumerate()
+        {
+            match state {
+                CellState::Green => {
+                    if word.get(position) != Some(&letter) {
+                        return Err(GuessError::MissingGreen { letter, position });
+                    }
+                    *revealed.entry(letter).or_insert(0) += 1;
+                }
+                CellState::Yellow => *revealed.entry(letter).or_insert(0) += 1,
+                _ => {}
+            }
+        }
+        for (letter, count) in revealed {
+            let needed = required.entry(letter).or_insert(0);
+            *needed = (*needed).max(count);
+        }
+    }
+
+    // Report missing letters in the order they were first revealed
+    for guess in history {
+        for letter in guess.word.chars() {
+            if let Some(&count) = required.get(&letter) {
+                if word.iter().filter(|&&c| c == letter).count() < count {
+                    return Err(GuessError::MissingLetter { letter, count });
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Arrangements of the on-screen keyboard.
+
+use crate::language::Language;
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyboardLayout {
+    /// The keyboard the language is usually typed on.
+    #[default]
+    Language,
+    Qwerty,
+    Qwertz,
+    Azerty,
+    Dvorak,
+    Colemak,
+}
+
+impl KeyboardLayout {
+    pub const ALL: [Self; 6] =
+        [Self::Language, Self::Qwerty, Self::Qwertz, Self::Azerty, Self::Dvorak, Self::Colemak];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Language => "Language default",
+            Self::Qwerty => "QWERTY",
+            Self::Qwertz => "QWERTZ",
+            Self::Azerty => "AZERTY",
+            Self::Dvorak => "Dvorak",
+            Self::Colemak => "Colemak",
+        }
+    }
+
+    /// The letter rows of the layout, top first. Punctuation keys are left out.
+    fn letter_rows(&self) -> Option<&'static [&'static str]> {
+        match self {
+            Self::Language => None,
+            Self::Qwerty => Some(&["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"]),
+            Self::Qwertz => Some(&["QWERTZUIOP", "ASDFGHJKL", "YXCVBNM"]),
+            Self::Azerty => Some(&["AZERTYUIOP", "QSDFGHJKLM", "WXCVBN"]),
+            Self::Dvorak => Some(&["PYFGCRL", "AOEUIDHTNS", "QJKXBMWVZ"]),
+            Self::Colemak => Some(&["QWFPGJLUY", "ARSTDHNEIO", "ZXCVBKM"]),
+        }
+    }
+
+    /// The keys to show for `language`, top row first. Letters of the language that the layout
+    /// doesn't have, like Ñ, go at the end of the row they're on in the language's own keyboard.
+    pub fn rows(&self, language: &Language) -> Vec<Vec<char>> {
+        let layout = match self.letter_rows() {
+            Some(layout) => layout,
+            None => return language.keyboard.iter().map(|row| row.chars().collect()).collect(),
+        };
+        let mut rows: Vec<Vec<char>> = layout
+            .iter()
+            .map(|row| row.chars().filter(|&c| language.alphabet.contains(c)).collect())
+            .collect();
+        for (i, row) in language.keyboard.iter().enumerate() {
+            for c in row.chars().filter(|&c| !layout.iter().any(|row| row.contains(c))) {
+                rows[i.min(layout.len() - 1)].push(c);
+            }
+        }
+        rows
+    }
+}
+
+/// Where the enter and delete keys go.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpecialKeys {
+    /// Either side of the bottom row, enter on the left.
+    #[default]
+    EnterLeft,
+    /// Either side of the bottom row, enter on the right.
+    EnterRight,
+    /// On a row of their own under the letters.
+    Below,
+}
+
+impl SpecialKeys {
+    pub const ALL: [Self; 3] = [Self::EnterLeft, Self::EnterRight, Self::Below];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EnterLeft => "Enter left, delete right",
+            Self::EnterRight => "Delete left, enter right",
+            Self::Below => "Below the letters",
+        }
+    }
+}
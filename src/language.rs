@@ -0,0 +1,116 @@
+//! The languages the game can be played in: which letters they use, how typed letters are
+//! folded into those, the on-screen keyboard and the built-in words.
+
+use crate::words::WordList;
+use once_cell::sync::OnceCell;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+pub struct Language {
+    /// Saved in the settings and added to challenge links.
+    pub code: &'static str,
+    pub name: &'static str,
+    /// Every letter a word may use, in uppercase.
+    pub alphabet: &'static str,
+    /// The on-screen keyboard, top row first.
+    pub keyboard: &'static [&'static str],
+    /// Uppercase letters that are typed or written in word lists but count as another letter,
+    /// such as accented vowels in Spanish.
+    pub folds: &'static [(char, char)],
+    /// The built-in answers and accepted guesses, one per line, all of the same length. English
+    /// uses the lists generated at build time instead.
+    words: Option<(&'static str, &'static str)>,
+    /// The built-in word lists, read the first time they're needed.
+    lists: OnceCell<Vec<Arc<WordList>>>,
+}
+
+pub static ENGLISH: Language = Language {
+    code: "en",
+    name: "English",
+    alphabet: "ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+    keyboard: &["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"],
+    folds: &[],
+    words: None,
+    lists: OnceCell::new(),
+};
+
+pub static SPANISH: Language = Language {
+    code: "es",
+    name: "Español",
+    alphabet: "ABCDEFGHIJKLMNÑOPQRSTUVWXYZ",
+    keyboard: &["QWERTYUIOP", "ASDFGHJKLÑ", "ZXCVBNM"],
+    folds: &[('Á', 'A'), ('É', 'E'), ('Í', 'I'), ('Ó', 'O'), ('Ú', 'U'), ('Ü', 'U')],
+    words: Some((include_str!("../words_es.txt"), include_str!("../guesses_es.txt"))),
+    lists: OnceCell::new(),
+};
+
+pub static GERMAN: Language = Language {
+    code: "de",
+    name: "Deutsch",
+    alphabet: "ABCDEFGHIJKLMNOPQRSTUVWXYZÄÖÜ",
+    keyboard: &["QWERTZUIOPÜ", "ASDFGHJKLÖÄ", "YXCVBNM"],
+    folds: &[],
+    words: Some((include_str!("../words_de.txt"), include_str!("../guesses_de.txt"))),
+    lists: OnceCell::new(),
+};
+
+pub static FINNISH: Language = Language {
+    code: "fi",
+    name: "Suomi",
+    alphabet: "ABCDEFGHIJKLMNOPQRSTUVWXYZÅÄÖ",
+    keyboard: &["QWERTYUIOPÅ", "ASDFGHJKLÖÄ", "ZXCVBNM"],
+    folds: &[],
+    words: Some((include_str!("../words_fi.txt"), include_str!("../guesses_fi.txt"))),
+    lists: OnceCell::new(),
+};
+
+pub static LANGUAGES: [&Language; 4] = [&ENGLISH, &SPANISH, &GERMAN, &FINNISH];
+
+impl Language {
+    pub fn from_code(code: &str) -> Option<&'static Language> {
+        LANGUAGES.iter().copied().find(|language| language.code == code)
+    }
+
+    /// The letter `c` counts as, if it belongs to this language at all. Letters that turn into
+    /// more than one when uppercased, like the German ß, never do.
+    pub fn fold(&self, c: char) -> Option<char> {
+        let mut upper = c.to_uppercase();
+        let c = match (upper.next(), upper.next()) {
+            (Some(c), None) => c,
+            _ => return None,
+        };
+        let c = self.folds.iter().find(|(from, _)| *from == c).map_or(c, |&(_, to)| to);
+        self.alphabet.contains(c).then_some(c)
+    }
+
+    /// Fold every letter of `word`, or report the first one that doesn't belong.
+    pub fn fold_word(&self, word: &str) -> Result<String, char> {
+        word.chars().map(|c| self.fold(c).ok_or(c)).collect()
+    }
+
+    /// The word lengths there are built-in words for.
+    pub fn word_lens(&self) -> RangeInclusive<usize> {
+        match self.words {
+            None => crate::MIN_WORD_LEN..=crate::MAX_WORD_LEN,
+            Some((answers, _)) => {
+                let len = answers
+                    .lines()
+                    .map(str::trim)
+                    .find(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map_or(0, |word| word.chars().count());
+                len..=len
+            }
+        }
+    }
+
+    /// The built-in words with `len` letters, if there are any.
+    pub fn word_list(&self, len: usize) -> Option<Arc<WordList>> {
+        let lists = self.lists.get_or_init(|| match self.words {
+            None => self.word_lens().filter_map(WordList::builtin).map(Arc::new).collect(),
+            Some((answers, guesses)) => {
+                WordList::custom(self, answers, Some(guesses)).into_iter().map(Arc::new).collect()
+            }
+        });
+        lists.iter().find(|words| words.word_len() == len).cloned()
+    }
+}
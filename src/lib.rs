@@ -3,14 +3,46 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+pub mod challenge;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
+pub mod daily;
 mod draw;
+pub mod engine;
+pub mod keyboard;
+pub mod language;
+pub mod qr;
+pub mod share;
+pub mod solver;
+pub mod stats;
+pub mod theme;
+pub mod timer;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tui;
 mod utils;
+pub mod words;
 
 pub use app::WordleApp;
 pub use utils::Args;
 
 include!(concat!(env!("OUT_DIR"), "/word_list.rs"));
 
+/// The words that can be picked as answers with `len` letters, if the game supports that length.
+pub fn answer_list(len: usize) -> Option<&'static [&'static str]> {
+    (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&len).then(|| ANSWER_LISTS[len - MIN_WORD_LEN])
+}
+
+/// The words that are accepted as guesses with `len` letters, if the game supports that length.
+/// Every answer is also a valid guess.
+pub fn guess_list(len: usize) -> Option<&'static [&'static str]> {
+    (MIN_WORD_LEN..=MAX_WORD_LEN).contains(&len).then(|| GUESS_LISTS[len - MIN_WORD_LEN])
+}
+
+/// Check an uppercase word against the accepted guesses for its length.
+pub fn is_word(word: &str) -> bool {
+    guess_list(word.chars().count()).is_some_and(|list| list.binary_search(&word).is_ok())
+}
+
 // ----------------------------------------------------------------------------
 // When compiling for web:
 
@@ -41,19 +73,7 @@ pub fn start(canvas_id: &str) -> Result<(), eframe::wasm_bindgen::JsValue> {
         &web_sys::window().expect("no global window").location().search()?,
     )?;
 
-    let word = if let Some(arg) = search_params.get("word") {
-        arg.to_uppercase()
-    } else if let Some(arg) = search_params.get("bword").map(|x| utils::decode(x).ok()).flatten() {
-        arg
-    } else {
-        String::new()
-    };
-
-    let app = if WORD_LIST.contains(&&*word) {
-        WordleApp::with_args(utils::Args { word: Some(word) })
-    } else {
-        WordleApp::default()
-    };
+    let app = WordleApp::with_args(challenge::link_args(|name| search_params.get(name)));
 
     eframe::start_web(canvas_id, Box::new(app))
 }
@@ -7,15 +7,30 @@ use std::env;
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let app = if args.len() > 1 && wordle_clone::WORD_LIST.contains(&&*args[1].to_uppercase()) {
-        wordle_clone::WordleApp::with_args(wordle_clone::Args {
-            word: Some(args[1].to_uppercase()),
-        })
-    } else {
-        wordle_clone::WordleApp::default()
+    use wordle_clone::cli;
+
+    let options = match cli::parse(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("error: {}\n\nRun with --help to see the options.", err);
+            std::process::exit(2);
+        }
     };
+    if options.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    let app_args = options.args;
+
+    if options.tui {
+        if let Err(err) = wordle_clone::tui::run(app_args) {
+            eprintln!("Terminal error: {}", err);
+            std::process::exit(1);
+        }
+        return;
+    }
 
+    let app = wordle_clone::WordleApp::with_args(app_args);
     let native_options = eframe::NativeOptions::default();
     eframe::run_native(Box::new(app), native_options);
 }
@@ -0,0 +1,61 @@
+//! Challenge QR codes as image files, for printing.
+
+use qrcode::Color;
+
+/// The blank border around a code, in modules, that scanners need to find it.
+const QUIET_ZONE: usize = 4;
+/// How many pixels wide each module of a PNG is, which is plenty for a poster.
+pub const PNG_SCALE: usize = 10;
+
+/// Whether the module at `x`, `y` of the bordered image is dark.
+fn is_dark(data: &[Color], width: usize, x: usize, y: usize) -> bool {
+    let inside = |i: usize| (QUIET_ZONE..QUIET_ZONE + width).contains(&i);
+    inside(x) && inside(y) && data[(y - QUIET_ZONE) * width + x - QUIET_ZONE] == Color::Dark
+}
+
+/// The code as an SVG, one unit per module. It scales to any size without blurring.
+pub fn to_svg(data: &[Color], width: usize) -> String {
+    let size = width + 2 * QUIET_ZONE;
+    let mut path = String::new();
+    for y in 0..size {
+        for x in (0..size).filter(|&x| is_dark(data, width, x, y)) {
+            path += &format!("M{} {}h1v1h-1z", x, y);
+        }
+    }
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            "\n",
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {0}" "#,
+            r#"shape-rendering="crispEdges">"#,
+            r##"<rect width="{0}" height="{0}" fill="#fff"/>"##,
+            r##"<path fill="#000" d="{1}"/></svg>"##,
+            "\n",
+        ),
+        size, path
+    )
+}
+
+/// The code as a black and white PNG, `PNG_SCALE` pixels per module.
+pub fn to_png(data: &[Color], width: usize) -> Vec<u8> {
+    let size = (width + 2 * QUIET_ZONE) * PNG_SCALE;
+    let pixels: Vec<u8> = (0..size * size)
+        .map(|i| {
+            let (x, y) = (i % size / PNG_SCALE, i / size / PNG_SCALE);
+            if is_dark(data, width, x, y) {
+                0
+            } else {
+                255
+            }
+        })
+        .collect();
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .expect("writing a PNG to memory can't fail");
+    bytes
+}
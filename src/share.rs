@@ -0,0 +1,78 @@
+//! Spoiler-free text summaries of finished games.
+
+use crate::engine::{Game, GameState, MultiGame, BOARDS_PER_ROW};
+use crate::theme::Theme;
+use crate::timer::{format_time, Timer, Timing};
+
+/// Build the familiar emoji grid for `game` in the colors of `theme`, headed by `title`, the score
+/// and a `*` in hard mode. Lost games score `X`.
+pub fn share_text(title: &str, game: &Game, theme: Theme, dark_mode: bool) -> String {
+    let mut text = header(title, game.state(), game.max_guesses(), game.rules().hard_mode);
+    for guess in game.history() {
+        text.push('\n');
+        text.extend(guess.pattern.states().into_iter().map(|state| theme.emoji(state, dark_mode)));
+    }
+    text
+}
+
+/// Build the summary of a game on several boards: the header as in [`share_text`], then how many
+/// guesses each board took, laid out like the boards. Unsolved boards are a red square.
+pub fn multi_share_text(title: &str, game: &MultiGame) -> String {
+    let mut text = header(title, game.state(), game.max_guesses(), game.rules().hard_mode);
+    for row in game.boards().chunks(BOARDS_PER_ROW) {
+        text.push('\n');
+        for board in row {
+            match board.state() {
+                GameState::Success(guesses) => text += &keycaps(*guesses),
+                _ => text.push('🟥'),
+            }
+        }
+    }
+    text
+}
+
+fn header(title: &str, state: &GameState, max_guesses: usize, hard_mode: bool) -> String {
+    let score = match state {
+        GameState::Success(guesses) => guesses.to_string(),
+        _ => "X".to_string(),
+    };
+    let mut text = format!("{} {}/{}", title, score, max_guesses);
+    if hard_mode {
+        text.push('*');
+    }
+    text.push('\n');
+    text
+}
+
+/// `n` in keycap emoji, with the single keycap for ten.
+fn keycaps(n: usize) -> String {
+    if n == 10 {
+        return "🔟".to_string();
+    }
+    n.to_string().chars().map(|digit| format!("{}\u{fe0f}\u{20e3}", digit)).collect()
+}
+
+/// A line for the end of the share text of a timed game: the total time, then how long each
+/// guess took.
+pub fn timing_text(splits: &[f64]) -> String {
+    let total = format_time(splits.iter().sum());
+    let splits: Vec<String> = splits.iter().map(|&split| format_time(split)).collect();
+    format!("⏱️ {} ({})", total, splits.join(" "))
+}
+
+/// The share text of a game played with `timer`: a speedrun's own text, or `text` with the times
+/// of a timed game's guesses added. Untimed games are shared as they are.
+pub fn timed_share_text(text: String, title: &str, timer: &Timer) -> String {
+    match timer.timing() {
+        Timing::Off => text,
+        Timing::Speedrun => speedrun_text(title, timer.solved(), timer.limit()),
+        Timing::Countdown if timer.splits().is_empty() => text,
+        Timing::Countdown => format!("{}\n{}", text, timing_text(timer.splits())),
+    }
+}
+
+/// The share text of a finished speedrun: how many words were solved, and in how long.
+pub fn speedrun_text(title: &str, solved: u32, seconds: f64) -> String {
+    let words = if solved == 1 { "word" } else { "words" };
+    format!("{} speedrun\n\n⏱️ {} {} in {}", title, solved, words, format_time(seconds))
+}
@@ -0,0 +1,152 @@
+//! Suggests guesses by narrowing down the possible answers and ranking words by how much they
+//! are expected to reveal.
+//!
+//! Ranking every allowed word against every possible answer is slow for the first guess or two,
+//! so a [`Solver`] does the work in steps that a front-end can spread over several frames.
+
+use crate::engine::{check_hard_mode, score_guess, Game, Guess, Pattern, Rules};
+use crate::words::WordList;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The words in `list` that would have produced every pattern in `history`.
+pub fn candidates<'a, S: AsRef<str>>(list: &'a [S], history: &[Guess]) -> Vec<&'a str> {
+    list.iter()
+        .map(AsRef::as_ref)
+        .filter(|word| history.iter().all(|guess| score_guess(&guess.word, word) == guess.pattern))
+        .collect()
+}
+
+/// The expected information, in bits, that guessing `guess` gives about which of `candidates` is
+/// the answer.
+pub fn entropy<S: AsRef<str>>(guess: &str, candidates: &[S]) -> f64 {
+    let mut counts: HashMap<Pattern, usize> = HashMap::new();
+    for answer in candidates {
+        *counts.entry(score_guess(guess, answer.as_ref())).or_insert(0) += 1;
+    }
+    let total = candidates.len() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub word: String,
+    /// Expected information in bits.
+    pub entropy: f64,
+    /// Whether this word could still be the answer.
+    pub possible: bool,
+}
+
+impl Suggestion {
+    /// Better suggestions come first. Possible answers win ties.
+    fn rank(&self, other: &Self) -> Ordering {
+        other.entropy.partial_cmp(&self.entropy).unwrap().then(other.possible.cmp(&self.possible))
+    }
+}
+
+pub struct Solver {
+    /// What the solver was built for: the word list, the rules and the guesses made so far.
+    words: Arc<WordList>,
+    word_len: usize,
+    rules: Rules,
+    guesses: Vec<String>,
+    candidates: Vec<String>,
+    pool: Vec<String>,
+    next: usize,
+    /// Kept sorted, best first.
+    ranked: Vec<Suggestion>,
+}
+
+impl Solver {
+    /// Prepare to rank the guesses of `game`'s word list, with its answers as the words that
+    /// could be the answer. Only the guesses made so far are used, never the answer itself. In
+    /// hard mode, words that break the rules are left out.
+    pub fn new(game: &Game) -> Self {
+        let words = game.words();
+        let candidates: Vec<String> =
+            candidates(words.answers(), game.history()).into_iter().map(String::from).collect();
+        let pool = if candidates.len() <= 2 {
+            // Nothing can beat guessing one of the answers outright
+            candidates.clone()
+        } else {
+            words
+                .guesses()
+                .iter()
+                .filter(|word| {
+                    !game.rules().hard_mode || check_hard_mode(word, game.history()).is_ok()
+                })
+                .map(|word| word.to_string())
+                .collect()
+        };
+        Self {
+            words: Arc::clone(words),
+            word_len: game.word_len(),
+            rules: game.rules().clone(),
+            guesses: game.history().iter().map(|guess| guess.word.clone()).collect(),
+            candidates,
+            pool,
+            next: 0,
+            ranked: Vec::new(),
+        }
+    }
+
+    /// Whether this solver was built for `game` as it is now: the same word list and rules, and
+    /// the same guesses.
+    pub fn is_for(&self, game: &Game) -> bool {
+        Arc::ptr_eq(&self.words, game.words())
+            && self.word_len == game.word_len()
+            && self.rules == *game.rules()
+            && self.guesses.len() == game.history().len()
+            && self.guesses.iter().zip(game.history()).all(|(a, b)| *a == b.word)
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next >= self.pool.len()
+    }
+
+    /// How much of the ranking is finished, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        if self.pool.is_empty() {
+            1.0
+        } else {
+            self.next as f32 / self.pool.len() as f32
+        }
+    }
+
+    /// Rank more words, stopping after roughly `budget` scorings.
+    pub fn step(&mut self, budget: usize) {
+        let per_word = self.candidates.len().max(1);
+        let end = self.next.saturating_add((budget / per_word).max(1)).min(self.pool.len());
+        for word in &self.pool[self.next..end] {
+            self.ranked.push(Suggestion {
+                word: word.clone(),
+                entropy: entropy(word, &self.candidates),
+                possible: self.candidates.contains(word),
+            });
+        }
+        // The words ranked before are already in order, so this only merges in the new ones
+        self.ranked.sort_by(Suggestion::rank);
+        self.next = end;
+    }
+
+    /// Run every remaining step at once.
+    pub fn finish(&mut self) {
+        self.step(usize::MAX);
+    }
+
+    /// The best `count` words ranked so far. Possible answers win ties.
+    pub fn suggestions(&self, count: usize) -> &[Suggestion] {
+        &self.ranked[..count.min(self.ranked.len())]
+    }
+}
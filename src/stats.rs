@@ -0,0 +1,56 @@
+//! Results of past games, kept across sessions.
+
+use crate::engine::GameState;
+use std::time::Duration;
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub played: u32,
+    pub wins: u32,
+    pub current_streak: u32,
+    pub max_streak: u32,
+    /// `distribution[i]` is the number of games won in `i + 1` guesses.
+    pub distribution: Vec<u32>,
+    /// The quickest win of a timed game, timed from the first letter typed.
+    pub fastest_win: Option<Duration>,
+    /// The most words solved in a speedrun.
+    pub best_speedrun: u32,
+}
+
+impl Stats {
+    /// Count a finished game. Games still being played are ignored.
+    pub fn record(&mut self, state: &GameState) {
+        match state {
+            GameState::Playing => return,
+            GameState::Success(guesses) => {
+                self.wins += 1;
+                self.current_streak += 1;
+                self.max_streak = self.max_streak.max(self.current_streak);
+                if self.distribution.len() < *guesses {
+                    self.distribution.resize(*guesses, 0);
+                }
+                self.distribution[guesses - 1] += 1;
+            }
+            GameState::Failure => self.current_streak = 0,
+        }
+        self.played += 1;
+    }
+
+    /// Count the time a game was won in.
+    pub fn record_time(&mut self, seconds: f64) {
+        let time = Duration::from_secs_f64(seconds.max(0.0));
+        self.fastest_win = Some(self.fastest_win.map_or(time, |fastest| fastest.min(time)));
+    }
+
+    /// Count a finished speedrun. Its words aren't counted as games of their own.
+    pub fn record_speedrun(&mut self, solved: u32) {
+        self.best_speedrun = self.best_speedrun.max(solved);
+    }
+
+    /// The share of games won, rounded to a whole percent.
+    pub fn win_percentage(&self) -> u32 {
+        (self.wins * 100 + self.played / 2).checked_div(self.played).unwrap_or(0)
+    }
+}
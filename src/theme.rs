@@ -0,0 +1,52 @@
+//! Color schemes for scored cells, shared by every front-end and the share text.
+
+use crate::engine::CellState;
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Theme {
+    #[default]
+    Classic,
+    /// Orange and blue instead of green and yellow, for players who can't tell those apart.
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Self; 2] = [Self::Classic, Self::HighContrast];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Classic => "Classic",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    /// The color of a scored cell, as red, green and blue. Cells that haven't been scored have
+    /// no color of their own, so they're left to the front-end.
+    pub fn rgb(&self, state: CellState, dark_mode: bool) -> Option<[u8; 3]> {
+        let rgb = match (self, state) {
+            (_, CellState::Empty) => return None,
+            (_, CellState::Gray) if dark_mode => [96, 96, 96],
+            (_, CellState::Gray) => [120, 124, 126],
+            (Self::Classic, CellState::Yellow) if dark_mode => [181, 159, 59],
+            (Self::Classic, CellState::Yellow) => [201, 180, 88],
+            (Self::Classic, CellState::Green) if dark_mode => [83, 141, 78],
+            (Self::Classic, CellState::Green) => [106, 170, 100],
+            (Self::HighContrast, CellState::Yellow) => [133, 192, 249],
+            (Self::HighContrast, CellState::Green) => [245, 121, 58],
+        };
+        Some(rgb)
+    }
+
+    /// The emoji square for a scored cell in the share text.
+    pub fn emoji(&self, state: CellState, dark_mode: bool) -> char {
+        match (self, state) {
+            (Self::Classic, CellState::Green) => '🟩',
+            (Self::Classic, CellState::Yellow) => '🟨',
+            (Self::HighContrast, CellState::Green) => '🟧',
+            (Self::HighContrast, CellState::Yellow) => '🟦',
+            _ if dark_mode => '⬛',
+            _ => '⬜',
+        }
+    }
+}
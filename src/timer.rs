@@ -0,0 +1,200 @@
+//! Clocks for timed games, and the time each guess took.
+//!
+//! Front-ends keep their own clocks, so times are passed in as seconds from whatever point they
+//! count from. Only differences between them are ever used.
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timing {
+    #[default]
+    Off,
+    /// Solve the word before the time runs out.
+    Countdown,
+    /// Solve as many words as possible before the time runs out.
+    Speedrun,
+}
+
+impl Timing {
+    pub const ALL: [Self; 3] = [Self::Off, Self::Countdown, Self::Speedrun];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Countdown => "Countdown",
+            Self::Speedrun => "Speedrun",
+        }
+    }
+}
+
+/// The shortest and longest time limits, in seconds.
+pub const MIN_TIME_LIMIT: u32 = 30;
+pub const MAX_TIME_LIMIT: u32 = 900;
+pub const DEFAULT_TIME_LIMIT: u32 = 180;
+
+/// The clock of a game, timed or not. It starts when the first letter is typed, not when the game
+/// does, so there's time to get ready.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timer {
+    timing: Timing,
+    limit: f64,
+    started: Option<f64>,
+    /// When the current word was started. A speedrun moves on to new words with the same clock.
+    word_started: f64,
+    /// How long each guess of the current word took.
+    splits: Vec<f64>,
+    /// How many words of a speedrun have been solved.
+    solved: u32,
+    stopped: Option<f64>,
+}
+
+impl Timer {
+    pub fn new(timing: Timing, limit_seconds: u32) -> Self {
+        Self { timing, limit: limit_seconds as f64, ..Self::default() }
+    }
+
+    pub fn timing(&self) -> Timing {
+        self.timing
+    }
+
+    pub fn limit(&self) -> f64 {
+        self.limit
+    }
+
+    /// Start the clock at `now`, unless it's already running. Untimed clocks never start.
+    pub fn start(&mut self, now: f64) {
+        if self.timing != Timing::Off && self.started.is_none() {
+            self.started = Some(now);
+            self.word_started = now;
+        }
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Stop the clock for good.
+    pub fn stop(&mut self, now: f64) {
+        if self.stopped.is_none() {
+            self.stopped = Some(now);
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped.is_some()
+    }
+
+    /// Seconds on the clock at `now`, which stops going up once the clock is stopped.
+    pub fn elapsed(&self, now: f64) -> f64 {
+        match self.started {
+            Some(started) => self.stopped.unwrap_or(now).min(now) - started,
+            None => 0.0,
+        }
+    }
+
+    /// Seconds left at `now`.
+    pub fn remaining(&self, now: f64) -> f64 {
+        (self.limit - self.elapsed(now)).max(0.0)
+    }
+
+    /// Whether the clock is still running at `now` but the time is up. Untimed games never run
+    /// out of time.
+    pub fn is_up(&self, now: f64) -> bool {
+        self.timing != Timing::Off
+            && !self.is_stopped()
+            && self.is_started()
+            && self.remaining(now) <= 0.0
+    }
+
+    /// Whether the clock is counting down, so it needs to be shown ticking.
+    pub fn is_running(&self) -> bool {
+        self.timing != Timing::Off && self.is_started() && !self.is_stopped()
+    }
+
+    /// Note that a guess was made at `now`. Untimed games can be left open between guesses, so
+    /// their guesses aren't timed.
+    pub fn split(&mut self, now: f64) {
+        if self.timing == Timing::Off {
+            return;
+        }
+        self.start(now);
+        let previous = self.word_started + self.splits.iter().sum::<f64>();
+        self.splits.push(now - previous);
+    }
+
+    /// How long each guess of the current word took, in seconds.
+    pub fn splits(&self) -> &[f64] {
+        &self.splits
+    }
+
+    /// Move a speedrun on to the next word at `now`, counting the last one if it was solved.
+    pub fn next_word(&mut self, now: f64, solved: bool) {
+        if solved {
+            self.solved += 1;
+        }
+        self.word_started = now;
+        self.splits.clear();
+    }
+
+    /// How many words of a speedrun have been solved.
+    pub fn solved(&self) -> u32 {
+        self.solved
+    }
+}
+
+/// A [`Timer`] as it was when the game was saved. Front-end clocks don't carry over between runs,
+/// so times are kept relative to when it was saved.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SavedTimer {
+    pub timing: Timing,
+    pub limit: f64,
+    /// Seconds on the clock, if it had started.
+    pub elapsed: Option<f64>,
+    /// Seconds since the current word was started.
+    pub word_elapsed: f64,
+    pub splits: Vec<f64>,
+    pub solved: u32,
+    pub stopped: bool,
+}
+
+impl Timer {
+    /// Save the clock as it is at `now`.
+    pub fn save(&self, now: f64) -> SavedTimer {
+        SavedTimer {
+            timing: self.timing,
+            limit: self.limit,
+            elapsed: self.started.map(|_| self.elapsed(now)),
+            word_elapsed: self
+                .started
+                .map_or(0.0, |_| self.stopped.unwrap_or(now) - self.word_started),
+            splits: self.splits.clone(),
+            solved: self.solved,
+            stopped: self.is_stopped(),
+        }
+    }
+
+    /// Pick a saved clock back up at `now`, with the time it had left. A clock that was running
+    /// carries on running.
+    pub fn restore(saved: &SavedTimer, now: f64) -> Self {
+        let started = saved.elapsed.map(|elapsed| now - elapsed);
+        Self {
+            timing: saved.timing,
+            limit: saved.limit,
+            started,
+            word_started: started.map_or(0.0, |_| now - saved.word_elapsed),
+            splits: saved.splits.clone(),
+            solved: saved.solved,
+            stopped: (saved.stopped && started.is_some()).then_some(now),
+        }
+    }
+}
+
+/// `seconds` as a clock shows them: minutes and seconds, or seconds and tenths under a minute.
+pub fn format_time(seconds: f64) -> String {
+    if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else {
+        let seconds = seconds as u64;
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
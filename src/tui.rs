@@ -0,0 +1,224 @@
+//! A terminal front-end for playing over SSH or on machines without a display.
+//!
+//! It plays by the same rules and word lists as the window, and when the `persistence` feature
+//! is enabled it reads and writes the same saved state, so settings and statistics are shared.
+
+use crate::app::{GameState, KeyboardKey, WordleCell};
+use crate::engine::BOARDS_PER_ROW;
+use crate::language::Language;
+use crate::theme::Theme;
+use crate::{utils, WordleApp};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "persistence")]
+use eframe::epi;
+
+/// Puts the terminal into raw mode for as long as it's alive, and always restores it, even if
+/// the game panics.
+struct RawTerminal;
+
+impl RawTerminal {
+    fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Play in the terminal until the player quits.
+pub fn run(args: utils::Args) -> io::Result<()> {
+    #[cfg(feature = "persistence")]
+    let mut storage = epi::file_storage::FileStorage::from_app_name(WordleApp::NAME);
+    #[allow(unused_mut)]
+    let mut app = WordleApp::with_args(args);
+    #[cfg(feature = "persistence")]
+    if let Some(storage) = &storage {
+        let args = app.args.clone();
+        app = epi::get_value(storage, epi::APP_KEY).unwrap_or_default();
+        app.args = args;
+        // The saved game stays with the window, so whatever it was isn't being played here
+        app.mode = crate::app::Mode::Free;
+    }
+    // What's in storage, so that starting the daily puzzle below is written back too
+    #[cfg(feature = "persistence")]
+    let mut written = (app.stats.clone(), app.last_daily);
+    if let Some(language) = app.args.language.as_deref().and_then(Language::from_code) {
+        app.settings.set_language(language);
+    }
+    app.load_word_lists_from_args();
+    if !app.start_from_args() {
+        app.reset_random_word();
+    }
+    // Shown in place of the usual message until the first key press
+    let mut notice = app.link_error.clone().or_else(|| {
+        app.word_list_window
+            .errors
+            .first()
+            .map(|error| format!("Couldn't use {}: {}", app.word_list_window.rejected, error))
+    });
+
+    // The window's clock counts from when it opened, and so does this one
+    let clock = Instant::now();
+    let _raw = RawTerminal::new()?;
+    let mut stdout = io::stdout();
+    loop {
+        let now = clock.elapsed().as_secs_f64();
+        app.check_timer(now);
+        // Only the statistics and the last daily puzzle started are written back, as soon as
+        // either changes. The rest is left alone, so a game left open in the window is still
+        // there next time
+        #[cfg(feature = "persistence")]
+        if let Some(storage) =
+            storage.as_mut().filter(|_| (&app.stats, app.last_daily) != (&written.0, written.1))
+        {
+            let mut stored: WordleApp = epi::get_value(storage, epi::APP_KEY).unwrap_or_default();
+            stored.stats = app.stats.clone();
+            stored.last_daily = stored.last_daily.max(app.last_daily);
+            epi::set_value(storage, epi::APP_KEY, &stored);
+            epi::Storage::flush(storage);
+            written = (app.stats.clone(), app.last_daily);
+        }
+        draw(&mut stdout, &app, notice.as_deref(), now)?;
+        // Redraw a ticking clock even when no keys are pressed
+        if app.timer.is_running() && !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key @ KeyEvent { kind: KeyEventKind::Press, .. }) => key,
+            _ => continue,
+        };
+        let now = clock.elapsed().as_secs_f64();
+        notice = None;
+        if key.code == KeyCode::Esc
+            || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+        {
+            break;
+        }
+
+        if app.game.is_over() {
+            if key.code == KeyCode::Enter {
+                app.reset_random_word();
+            }
+            continue;
+        }
+        match key.code {
+            KeyCode::Char(c) if app.input.chars().count() < app.game.word_len() => {
+                if let Some(letter) = app.settings.language().fold(c) {
+                    app.input.push(letter);
+                    app.error = None;
+                    app.timer.start(now);
+                }
+            }
+            KeyCode::Backspace => {
+                app.input.pop();
+                app.error = None;
+            }
+            KeyCode::Enter => {
+                app.submit_guess(now);
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn draw(out: &mut impl Write, app: &WordleApp, notice: Option<&str>, now: f64) -> io::Result<()> {
+    // Raw mode doesn't return the cursor to the start of the line by itself
+    const NEWLINE: &str = "\r\n";
+    queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+
+    queue!(out, Print(" "), Print(WordleApp::NAME))?;
+    if let Some(clock) = app.clock_text(now) {
+        queue!(out, Print("   "), Print(clock))?;
+    }
+    queue!(out, Print(NEWLINE), Print(NEWLINE))?;
+    // Boards are side by side, a few to a row, like in the window
+    for boards in app.boards().chunks(BOARDS_PER_ROW) {
+        for i in 0..app.game.max_guesses() {
+            for board in boards {
+                queue!(out, Print(" "))?;
+                for cell in &board[i] {
+                    draw_cell(out, cell, app.settings.theme)?;
+                }
+            }
+            queue!(out, Print(NEWLINE))?;
+        }
+        queue!(out, Print(NEWLINE))?;
+    }
+
+    let message = match (notice, &app.error, app.game.state()) {
+        (Some(notice), _, _) => notice.to_string(),
+        (None, Some(error), _) => error.to_string(),
+        (None, None, GameState::Playing) => app.candidates_left().unwrap_or_default(),
+        (None, None, state) => state.get_state_text(&app.game.answers()),
+    };
+    queue!(out, Print(" "), Print(message), Print(NEWLINE))?;
+    queue!(out, Print(" "), Print(app.splits_text().unwrap_or_default()), Print(NEWLINE))?;
+    queue!(out, Print(NEWLINE))?;
+
+    // Each row is shifted right of the one above, like a real keyboard
+    for (indent, row) in app.keyboard().iter().enumerate() {
+        queue!(out, Print(" ".repeat(1 << indent)))?;
+        for key in row {
+            draw_key(out, key, app.settings.theme)?;
+        }
+        queue!(out, Print(NEWLINE))?;
+    }
+
+    let help = if app.game.is_over() {
+        "Enter: new game   Esc: quit".to_string()
+    } else {
+        format!(
+            "Type letters, Backspace to delete, Enter to guess, Esc to quit.   Played {}, won {}%",
+            app.stats.played,
+            app.stats.win_percentage()
+        )
+    };
+    queue!(
+        out,
+        Print(NEWLINE),
+        SetForegroundColor(Color::DarkGrey),
+        Print(" "),
+        Print(help),
+        ResetColor
+    )?;
+    out.flush()
+}
+
+/// Draw a letter on its colored background. Cells that haven't been scored have no background.
+fn draw_cell(out: &mut impl Write, cell: &WordleCell, theme: Theme) -> io::Result<()> {
+    let background = theme.rgb(cell.state, true).map(|[r, g, b]| Color::Rgb { r, g, b });
+    let letter = if cell.letter == ' ' { '·' } else { cell.letter };
+    if let Some(background) = background {
+        queue!(out, SetBackgroundColor(background), SetForegroundColor(Color::White))?;
+    }
+    queue!(out, Print(format!(" {} ", letter)), ResetColor, Print(" "))
+}
+
+/// Draw a key of the keyboard. With one board it's a cell like those of the board; with more,
+/// the letter is followed by a colored block for each board.
+fn draw_key(out: &mut impl Write, key: &KeyboardKey, theme: Theme) -> io::Result<()> {
+    if let [state] = key.states[..] {
+        return draw_cell(out, &WordleCell { state, letter: key.letter }, theme);
+    }
+    queue!(out, Print(key.letter))?;
+    for &state in &key.states {
+        match theme.rgb(state, true) {
+            Some([r, g, b]) => queue!(out, SetBackgroundColor(Color::Rgb { r, g, b }), Print(" "))?,
+            None => queue!(out, Print("·"))?,
+        }
+        queue!(out, ResetColor)?;
+    }
+    queue!(out, Print(" "))
+}
@@ -1,256 +1,729 @@
-use crate::app::{CellState, GameState, WordleCell};
+use crate::app::{CellState, KeyboardKey, Mode, WordleCell};
+use crate::challenge::{self, Challenge};
+use crate::daily;
+use crate::draw::BoardAnimation;
+use crate::engine::{self, Game, GameState, MultiGame, Rules};
+use crate::language::ENGLISH;
+use crate::share::{multi_share_text, share_text, timed_share_text};
+use crate::timer::{format_time, Timer, Timing};
+use crate::words::WordList;
 use crate::WordleApp;
-use eframe::egui::{Key, Ui};
+use eframe::egui::{Context, Event, Key, Ui};
 use qrcode::QrCode;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use std::collections::HashMap;
-
-const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
-const KBD_ROW1: &str = "QWERTYUIOP";
-const KBD_ROW2: &str = "ASDFGHJKL";
-const KBD_ROW3: &str = "ZXCVBNM";
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
 
 impl WordleApp {
     pub(crate) fn handle_keys(&mut self, ctx: &Ui) {
-        let mut found_char = false;
-        // Check for pressed letter keys
-        for letter in LETTERS.chars() {
-            // Should be fine since it should only be an uppercase letter (from LETTERS)
-            if (ctx.input().key_released(get_key_from_char(letter).unwrap())
-                || self.kbd_keydown == letter.to_string())
-                && self.next_cell.0 < 6
-                && self.next_cell.1 < 5
-            {
+        let word_len = self.game.word_len();
+        let language = self.settings.language();
+        let now = ctx.input().time;
+        // Letters come from text events rather than key codes, so that letters with no key of
+        // their own on an English keyboard, like Ñ or Ä, can be typed
+        let mut letters: Vec<char> = ctx
+            .input()
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Text(text) => Some(text.chars().filter_map(|c| language.fold(c))),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        // On-screen letter keys hold a single letter; ENT and DEL are handled below
+        let mut clicked = self.kbd_keydown.chars();
+        if let (Some(letter), None) = (clicked.next(), clicked.next()) {
+            letters.push(letter);
+        }
+        for &letter in &letters {
+            if self.input.chars().count() < word_len {
                 // Write the pressed letter and advance the cursor
-                self.cells[self.next_cell.0][self.next_cell.1].letter = letter;
-                self.next_cell.1 += 1;
-                found_char = true;
-                break;
+                self.input.push(letter);
+                self.error = None;
+                self.typed_at = Some(now);
+                self.timer.start(now);
             }
         }
         // Check for pressed non-letter keys
-        if !found_char {
+        if letters.is_empty() {
             if (ctx.input().key_released(Key::Backspace) || self.kbd_keydown == "DEL")
-                && self.next_cell.1 > 0
+                && !self.input.is_empty()
             {
                 // Delete letter
-                self.next_cell.1 -= 1;
-                self.cells[self.next_cell.0][self.next_cell.1].letter = ' ';
-            } else if (ctx.input().key_released(Key::Enter) || self.kbd_keydown == "ENT")
-                && self.next_cell.1 >= 5
-            {
-                // Check a completed word
-                if check_word(
-                    &mut self.cells[self.next_cell.0],
-                    &self.word,
-                    &mut self.keyboard,
-                    &mut self.keyboard_state,
-                ) {
-                    if self.cells[self.next_cell.0]
-                        .iter()
-                        .all(|x| matches!(x.state, CellState::Green))
-                    {
-                        self.game_state = GameState::Success(self.next_cell.0 + 1);
-                    } else if self.next_cell.0 == 5 {
-                        self.game_state = GameState::Failure;
-                    }
-                    self.next_cell.0 += 1;
+                self.input.pop();
+                self.error = None;
+                self.typed_at = None;
+            } else if ctx.input().key_pressed(Key::Enter) || self.kbd_keydown == "ENT" {
+                if self.submit_guess(now) {
+                    self.scored_at = Some(now);
+                    self.typed_at = None;
                 } else {
-                    for cell in self.cells[self.next_cell.0].iter_mut() {
-                        cell.letter = ' ';
-                    }
+                    self.rejected_at = Some(now);
                 }
-                self.next_cell.1 = 0;
             }
         }
     }
 
-    pub(crate) fn reset_random_word(&mut self) {
-        self.word = get_random_word();
-        self.reset();
+    /// Submit the typed row, even if it's too short. A rejected row is kept as typed, with the
+    /// reason in `error`. Returns whether the guess was accepted. `now` is the time on the
+    /// front-end's clock, in seconds.
+    pub(crate) fn submit_guess(&mut self, now: f64) -> bool {
+        match self.game.guess(&self.input) {
+            Ok(_) => {
+                self.input.clear();
+                self.error = None;
+                self.timer.split(now);
+                if self.game.is_over() && self.timer.timing() == Timing::Speedrun {
+                    self.next_speedrun_word(now);
+                } else if self.game.is_over() {
+                    self.finish_game(now);
+                }
+                true
+            }
+            Err(error) => {
+                self.error = Some(error);
+                false
+            }
+        }
     }
 
-    #[rustfmt::skip] // rustfmt wants to split the keyboard initialization into loads of tiny lines
-    pub(crate) fn reset(&mut self) {
-        self.cells = Default::default();
-        self.next_cell = (0, 0);
-        self.keyboard = (
-            KBD_ROW1.chars().map(WordleCell::keyboard)
-                .collect::<Vec<WordleCell>>().try_into().unwrap(),
-            KBD_ROW2.chars().map(WordleCell::keyboard)
-                .collect::<Vec<WordleCell>>().try_into().unwrap(),
-            KBD_ROW3.chars().map(WordleCell::keyboard)
-                .collect::<Vec<WordleCell>>().try_into().unwrap(),
-        );
-        self.keyboard_state = LETTERS.chars().zip(std::iter::repeat(CellState::Empty)).collect();
-        self.game_state = GameState::Playing;
+    /// What is moving on each board right now: the last scored row flips, the last typed letter
+    /// pops and a rejected row shakes. Boards that are already solved stay still.
+    pub(crate) fn board_animations(&self, ui: &Ui) -> Vec<BoardAnimation> {
+        let guesses = self.game.guesses().len();
+        let now = ui.input().time;
+        self.game
+            .boards()
+            .iter()
+            .map(|board| {
+                let row = board.history().len();
+                let playing = !board.is_over();
+                // Only boards that were still playing got the last guess
+                let scored = self.scored_at.filter(|_| row > 0 && row == guesses);
+                BoardAnimation {
+                    now,
+                    scored: scored.map(|at| (row - 1, at)),
+                    won: scored.is_some() && matches!(board.state(), GameState::Success(_)),
+                    typed: self
+                        .typed_at
+                        .filter(|_| playing && !self.input.is_empty())
+                        .map(|at| (row, self.input.chars().count() - 1, at)),
+                    rejected: self.rejected_at.filter(|_| playing).map(|at| (row, at)),
+                }
+            })
+            .collect()
     }
 
-    pub fn with_args(args: Args) -> Self {
-        let mut app = Self::default();
-        app.args = args;
-        app
+    /// Build the grid of cells to draw for each board: every scored guess, then the row being
+    /// typed if the board is still playing, then empty rows up to the guess limit.
+    pub(crate) fn boards(&self) -> Vec<Vec<Vec<WordleCell>>> {
+        let word_len = self.game.word_len();
+        let max_guesses = self.game.max_guesses();
+        self.game
+            .boards()
+            .iter()
+            .map(|board| {
+                let mut rows: Vec<Vec<WordleCell>> = board
+                    .history()
+                    .iter()
+                    .map(|guess| {
+                        guess
+                            .word
+                            .chars()
+                            .zip(guess.pattern.states())
+                            .map(|(letter, state)| WordleCell { state, letter })
+                            .collect()
+                    })
+                    .collect();
+                if !board.is_over() {
+                    let mut row: Vec<WordleCell> =
+                        self.input.chars().map(WordleCell::keyboard).collect();
+                    row.resize_with(word_len, Default::default);
+                    rows.push(row);
+                }
+                rows.resize_with(max_guesses, || {
+                    (0..word_len).map(|_| WordleCell::default()).collect()
+                });
+                rows
+            })
+            .collect()
+    }
+
+    /// Build the on-screen keyboard in the chosen layout, top row first, with the best state
+    /// each letter has reached on each board.
+    pub(crate) fn keyboard(&self) -> Vec<Vec<KeyboardKey>> {
+        let letter_states = self.game.letter_states();
+        let key = |letter| KeyboardKey {
+            letter,
+            states: letter_states
+                .iter()
+                .map(|states| states.get(&letter).copied().unwrap_or(CellState::Empty))
+                .collect(),
+        };
+        let rows = self.settings.keyboard_layout.rows(self.settings.language());
+        rows.into_iter().map(|row| row.into_iter().map(key).collect()).collect()
+    }
+
+    /// The words to check guesses of `len` letters against: the custom word lists if they're for
+    /// that length, or else the current language's built-in ones.
+    pub(crate) fn words_for(&self, len: usize) -> Arc<WordList> {
+        match &self.custom_words {
+            Some(words) if words.word_len() == len => words.clone(),
+            _ => self
+                .settings
+                .language()
+                .word_list(len)
+                .unwrap_or_else(|| Arc::new(WordList::empty(len))),
+        }
+    }
+
+    /// A game of `word` that checks guesses against [`words_for`](Self::words_for) its length.
+    pub(crate) fn make_game(&self, word: &str, rules: Rules) -> MultiGame {
+        Game::with_words(word, rules, self.words_for(word.chars().count())).into()
+    }
+
+    /// A game with a board for each of `answers`, with the guess limit of `rules` raised to
+    /// allow for the extra boards.
+    pub(crate) fn make_boards(&self, answers: &[String], mut rules: Rules) -> MultiGame {
+        rules.max_guesses = engine::scaled_max_guesses(rules.max_guesses, answers.len());
+        let words = self.words_for(answers[0].chars().count());
+        MultiGame::new(answers, rules, words)
+    }
+
+    /// Start a new game for `word` using the current settings.
+    pub(crate) fn new_game(&mut self, word: &str) {
+        self.start_free_game(self.make_game(word, self.settings.rules()));
+    }
+
+    fn start_free_game(&mut self, game: MultiGame) {
+        self.game = game;
+        self.timer = Timer::new(self.settings.timing, self.settings.time_limit);
+        self.mode = Mode::Free;
+        self.challenge_message = None;
+        self.input.clear();
+        self.error = None;
+    }
+
+    /// Start today's daily puzzle, or carry on with it if it's being played. Returns false if it
+    /// has already been started before, so it can't be replayed. Everyone gets the same board, so
+    /// only hard mode is taken from the settings.
+    pub(crate) fn start_daily(&mut self) -> bool {
+        let number = daily::todays_puzzle();
+        if matches!(self.mode, Mode::Daily(playing) if playing == number) && !self.game.is_over() {
+            return true;
+        }
+        if self.last_daily == Some(number) {
+            return false;
+        }
+        // Counted as played straight away, so leaving it for a new game doesn't allow a retry
+        self.last_daily = Some(number);
+        let rules = Rules { hard_mode: self.settings.hard_mode, ..Rules::default() };
+        // Always the built-in five letter words, even if custom lists are loaded
+        let words = self.settings.language().word_list(5);
+        let words = words.unwrap_or_else(|| ENGLISH.word_list(5).unwrap());
+        let word = daily::daily_word(number, words.answers());
+        self.game = Game::with_words(word, rules, words.clone()).into();
+        // Everyone gets the same time too: as long as they like
+        self.timer = Timer::default();
+        self.mode = Mode::Daily(number);
+        self.challenge_message = None;
+        self.input.clear();
+        self.error = None;
+        true
+    }
+
+    /// Record the result of a game that has just ended at `now`, then show the statistics.
+    pub(crate) fn finish_game(&mut self, now: f64) {
+        self.timer.stop(now);
+        self.stats.record(self.game.state());
+        // Only timed games count, since untimed ones can be left open between guesses
+        if matches!(self.game.state(), GameState::Success(_)) && self.timer.timing() != Timing::Off
+        {
+            self.stats.record_time(self.timer.elapsed(now));
+        }
+        self.stats_open = true;
+    }
+
+    /// Move a speedrun on to a new word once the last one is over, keeping the clock running.
+    fn next_speedrun_word(&mut self, now: f64) {
+        let mut timer = std::mem::take(&mut self.timer);
+        timer.next_word(now, matches!(self.game.state(), GameState::Success(_)));
+        self.reset_random_word();
+        self.timer = timer;
+    }
+
+    /// End the game if its time ran out before `now`. A speedrun counts the words solved in
+    /// time; its words aren't counted as games of their own.
+    pub(crate) fn check_timer(&mut self, now: f64) {
+        if !self.timer.is_up(now) {
+            return;
+        }
+        self.game.resign();
+        self.input.clear();
+        if self.timer.timing() == Timing::Speedrun {
+            self.timer.stop(now);
+            self.stats.record_speedrun(self.timer.solved());
+            self.stats_open = true;
+        } else {
+            self.finish_game(now);
+        }
+    }
+
+    /// The clock of a timed game, with the words solved so far in a speedrun.
+    pub(crate) fn clock_text(&self, now: f64) -> Option<String> {
+        let remaining = format_time(self.timer.remaining(now));
+        match self.timer.timing() {
+            Timing::Off => None,
+            Timing::Countdown => Some(format!("⏱ {}", remaining)),
+            Timing::Speedrun => Some(format!("⏱ {}   {} solved", remaining, self.timer.solved())),
+        }
+    }
+
+    /// How long each guess of the current word took, in a timed game.
+    pub(crate) fn splits_text(&self) -> Option<String> {
+        if self.timer.timing() == Timing::Off {
+            return None;
+        }
+        let splits = self.timer.splits();
+        let splits: Vec<String> = splits.iter().map(|&split| format_time(split)).collect();
+        (!splits.is_empty()).then(|| format!("Splits: {}", splits.join(" · ")))
     }
-}
 
-pub(crate) fn promote_cell_state(cell: &mut CellState, state: CellState) {
-    match state {
-        CellState::Empty => {}
-        CellState::Gray => {
-            if matches!(cell, CellState::Empty) {
-                *cell = state;
+    fn random_word_with<R: Rng>(&self, len: usize, rng: &mut R) -> String {
+        let words = self.words_for(len);
+        match words.answers().choose(rng) {
+            Some(word) => word.to_string(),
+            None => get_random_word(len),
+        }
+    }
+
+    /// `count` different random answers of `len` letters, for as long as there are enough.
+    fn random_answers_with<R: Rng>(&self, len: usize, count: usize, rng: &mut R) -> Vec<String> {
+        let words = self.words_for(len);
+        let mut answers: Vec<String> =
+            words.answers().choose_multiple(rng, count).map(|word| word.to_string()).collect();
+        while answers.len() < count {
+            answers.push(self.random_word_with(len, rng));
+        }
+        answers
+    }
+
+    /// Start the game the command line or link asked for, if it asked for one. Its rules are the
+    /// settings with any given in the arguments on top.
+    pub(crate) fn start_from_args(&mut self) -> bool {
+        if self.args.daily {
+            if self.start_daily() {
+                return true;
             }
+            self.link_error = Some(format!(
+                "Today's daily puzzle #{} has already been played.",
+                daily::todays_puzzle()
+            ));
+            return false;
+        }
+        if let Some((timing, limit)) = self.args.timing {
+            self.settings.timing = timing;
+            self.settings.time_limit = limit;
         }
-        CellState::Yellow => {
-            if matches!(cell, CellState::Empty | CellState::Gray) {
-                *cell = state;
+        let len = self.args.word_len.unwrap_or(self.settings.word_len);
+        let boards = self.args.boards.unwrap_or(self.settings.boards);
+        // Challenges are a single word; anything random is played on the boards in the settings
+        // Links that bring their own word lists can only check the word once the lists are here
+        let word = self
+            .args
+            .word
+            .clone()
+            .filter(|word| self.words_for(word.chars().count()).is_guess(word));
+        if word.is_none() && self.args.word.is_some() {
+            let list = match &self.custom_words {
+                Some(_) => "custom",
+                None => self.settings.language().name,
+            };
+            self.link_error = Some(format!("The challenge word isn't in the {} word list", list));
+        }
+        let answers = match (word, self.args.seed) {
+            (Some(word), _) => Some(vec![word]),
+            (None, Some(seed)) => {
+                Some(self.random_answers_with(len, boards, &mut StdRng::seed_from_u64(seed)))
             }
+            // Asking for other rules starts a new game with them
+            (None, None)
+                if self.args.word_len.is_some()
+                    || self.args.max_guesses.is_some()
+                    || self.args.hard_mode.is_some()
+                    || self.args.boards.is_some()
+                    || self.args.adversarial
+                    || self.args.timing.is_some() =>
+            {
+                None
+            }
+            (None, None) => return false,
+        };
+        let mut rules = self.settings.rules();
+        if let Some(max_guesses) = self.args.max_guesses {
+            rules.max_guesses = max_guesses;
         }
-        CellState::Green => {
-            *cell = state;
+        if let Some(hard_mode) = self.args.hard_mode {
+            rules.hard_mode = hard_mode;
         }
+        let game = match answers {
+            Some(answers) => self.make_boards(&answers, rules),
+            None if self.args.adversarial || self.settings.adversarial => {
+                self.make_adversarial(len, rules)
+            }
+            None => {
+                let answers = self.random_answers_with(len, boards, &mut rand::thread_rng());
+                self.make_boards(&answers, rules)
+            }
+        };
+        self.start_free_game(game);
+        self.challenge_message = self.args.message.clone();
+        true
     }
-}
 
-// fn get_random_word() -> String {
-//     crate::WORD_LIST[WyRand::new().generate_range(0_usize..crate::WORD_LIST.len())].to_string()
-// }
-pub(crate) fn get_random_word() -> String {
-    crate::WORD_LIST.choose(&mut rand::thread_rng()).unwrap().to_string()
-}
+    /// A game of `len` letters that picks its answer as late as it can.
+    pub(crate) fn make_adversarial(&self, len: usize, rules: Rules) -> MultiGame {
+        Game::adversarial(rules, self.words_for(len)).into()
+    }
 
-pub(crate) fn get_key_from_char(c: char) -> Option<Key> {
-    match c {
-        'A' => Some(Key::A),
-        'B' => Some(Key::B),
-        'C' => Some(Key::C),
-        'D' => Some(Key::D),
-        'E' => Some(Key::E),
-        'F' => Some(Key::F),
-        'G' => Some(Key::G),
-        'H' => Some(Key::H),
-        'I' => Some(Key::I),
-        'J' => Some(Key::J),
-        'K' => Some(Key::K),
-        'L' => Some(Key::L),
-        'M' => Some(Key::M),
-        'N' => Some(Key::N),
-        'O' => Some(Key::O),
-        'P' => Some(Key::P),
-        'Q' => Some(Key::Q),
-        'R' => Some(Key::R),
-        'S' => Some(Key::S),
-        'T' => Some(Key::T),
-        'U' => Some(Key::U),
-        'V' => Some(Key::V),
-        'W' => Some(Key::W),
-        'X' => Some(Key::X),
-        'Y' => Some(Key::Y),
-        'Z' => Some(Key::Z),
-        _ => None,
+    /// How many words an adversarial game still has to choose from, while it's being played.
+    pub(crate) fn candidates_left(&self) -> Option<String> {
+        match self.game.boards() {
+            [game] if !game.is_over() => match game.candidates()?.len() {
+                1 => Some("1 possible answer left".to_string()),
+                n => Some(format!("{} possible answers left", n)),
+            },
+            _ => None,
+        }
     }
-}
 
-fn check_word(
-    word: &mut [WordleCell; 5],
-    correct: &str,
-    keyboard: &mut ([WordleCell; 10], [WordleCell; 9], [WordleCell; 7]),
-    keyboard_state: &mut HashMap<char, CellState>,
-) -> bool {
-    // Verify word is in list
-    if !crate::WORD_LIST.contains(&&*word.iter().map(|x| x.letter).collect::<String>()) {
-        return false;
+    /// Start a new game with random answers, on as many boards as the settings ask for, or an
+    /// adversarial one.
+    pub(crate) fn reset_random_word(&mut self) {
+        if self.settings.adversarial {
+            let game = self.make_adversarial(self.settings.word_len, self.settings.rules());
+            return self.start_free_game(game);
+        }
+        let answers = self.random_answers_with(
+            self.settings.word_len,
+            self.settings.boards,
+            &mut rand::thread_rng(),
+        );
+        self.start_free_game(self.make_boards(&answers, self.settings.rules()));
     }
 
-    // Start by assuming no letters match
-    for letter in word.iter_mut() {
-        letter.state = CellState::Gray;
-        promote_cell_state(keyboard_state.get_mut(&letter.letter).unwrap(), CellState::Gray)
+    pub(crate) fn reset(&mut self) {
+        let words = self.game.words().clone();
+        self.game = match self.game.boards() {
+            [game] if game.is_adversarial() => {
+                Game::adversarial(game.rules().clone(), words).into()
+            }
+            _ => MultiGame::new(&self.game.answers(), self.game.rules().clone(), words),
+        };
+        self.timer = Timer::new(self.timer.timing(), self.timer.limit() as u32);
+        self.input.clear();
+        self.error = None;
     }
 
-    for (i, correct_letter) in correct.chars().enumerate() {
-        if word[i].letter == correct_letter {
-            // If a letter matches exactly, turn it green
-            word[i].state = CellState::Green;
-            keyboard_state.insert(word[i].letter, CellState::Green);
-        } else {
-            for letter in word.iter_mut() {
-                // Look for a matching cell somewhere else in the word. If one is found and it's
-                // gray, turn it yellow.
-                if letter.letter == correct_letter && matches!(letter.state, CellState::Gray) {
-                    letter.state = CellState::Yellow;
-                    promote_cell_state(
-                        keyboard_state.get_mut(&letter.letter).unwrap(),
-                        CellState::Yellow,
-                    );
-                    break;
+    /// Copy an emoji summary of the finished game to the clipboard, and show it in a window in
+    /// case the clipboard isn't available.
+    pub(crate) fn share(&mut self, ctx: &Context, dark_mode: bool) {
+        let name = match self.game.boards() {
+            [game] if game.is_adversarial() => "Absurdle Clone",
+            _ => "Wordle Clone",
+        };
+        let title = match self.mode {
+            Mode::Daily(number) => format!("{} #{}", name, number),
+            Mode::Free if self.game.word_len() != 5 => {
+                format!("{} ({} letters)", name, self.game.word_len())
+            }
+            Mode::Free => name.to_string(),
+        };
+        let text = match self.game.boards() {
+            [game] => share_text(&title, game, self.settings.theme, dark_mode),
+            _ => multi_share_text(&title, &self.game),
+        };
+        let text = timed_share_text(text, &title, &self.timer);
+        ctx.output().copied_text = text.clone();
+        self.share_window.text = text;
+        self.share_window.open = true;
+    }
+
+    /// Play with the word lists in `answers` and `guesses` from now on, starting a new game, or
+    /// show why they can't be used. `source` names where they came from.
+    pub(crate) fn use_word_lists(&mut self, source: String, answers: &str, guesses: Option<&str>) {
+        if self.load_word_lists(source, answers, guesses) {
+            self.reset_random_word();
+        }
+    }
+
+    /// Like [`use_word_lists`](Self::use_word_lists), but leaves starting a game to the caller.
+    /// Returns whether the lists are being used.
+    fn load_word_lists(&mut self, source: String, answers: &str, guesses: Option<&str>) -> bool {
+        match WordList::custom(self.settings.language(), answers, guesses) {
+            Ok(words) => {
+                self.settings.word_len = words.word_len();
+                self.custom_words = Some(Arc::new(words));
+                self.word_list_window.source = source;
+                self.word_list_window.errors.clear();
+                true
+            }
+            Err(errors) => {
+                self.show_word_list_errors(
+                    source,
+                    errors.iter().map(ToString::to_string).collect(),
+                );
+                false
+            }
+        }
+    }
+
+    pub(crate) fn use_builtin_word_lists(&mut self) {
+        self.custom_words = None;
+        self.word_list_window.source.clear();
+        self.reset_random_word();
+    }
+
+    pub(crate) fn show_word_list_errors(&mut self, source: String, errors: Vec<String>) {
+        self.word_list_window.rejected = source;
+        self.word_list_window.errors = errors;
+        self.word_list_window.open = true;
+    }
+
+    /// Load the word lists named in the arguments. Natively they're files, read straight away.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn load_word_lists_from_args(&mut self) {
+        let read = |path: &String| {
+            std::fs::read_to_string(path).map_err(|err| format!("Couldn't read {}: {}", path, err))
+        };
+        match (&self.args.answers, &self.args.guesses) {
+            (Some(answers_path), guesses_path) => {
+                let answers = read(answers_path);
+                let guesses = guesses_path.as_ref().map(read).transpose();
+                match (answers, guesses) {
+                    (Ok(answers), Ok(guesses)) => {
+                        self.load_word_lists(answers_path.clone(), &answers, guesses.as_deref());
+                    }
+                    (Err(err), _) | (_, Err(err)) => {
+                        self.show_word_list_errors(answers_path.clone(), vec![err])
+                    }
                 }
             }
+            (None, Some(guesses_path)) => self.show_word_list_errors(
+                guesses_path.clone(),
+                vec!["A guess list needs an answer list to go with it".to_string()],
+            ),
+            (None, None) => {}
         }
     }
 
-    // Update the keyboard keys based on the their state from the alphabet
-    for key in keyboard.0.iter_mut() {
-        key.state = keyboard_state.get(&key.letter).unwrap().clone();
+    /// Whether the word lists named in the arguments are still being fetched.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn word_lists_pending(&self) -> bool {
+        false
     }
-    for key in keyboard.1.iter_mut() {
-        key.state = keyboard_state.get(&key.letter).unwrap().clone();
+
+    /// Whether the word lists named in the arguments are still being fetched.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn word_lists_pending(&self) -> bool {
+        self.word_list_window.download.is_some()
     }
-    for key in keyboard.2.iter_mut() {
-        key.state = keyboard_state.get(&key.letter).unwrap().clone();
+
+    /// Load the word lists named in the arguments. On the web they're URLs, and the downloads
+    /// are picked up by [`poll_word_lists`](Self::poll_word_lists) once they arrive.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn load_word_lists_from_args(&mut self) {
+        match (&self.args.answers, &self.args.guesses) {
+            (Some(answers), guesses) => {
+                self.word_list_window.download =
+                    Some((answers.clone(), download(answers), guesses.as_deref().map(download)));
+            }
+            (None, Some(guesses)) => self.show_word_list_errors(
+                guesses.clone(),
+                vec!["A guess list needs an answer list to go with it".to_string()],
+            ),
+            (None, None) => {}
+        }
+    }
+
+    /// Use the downloaded word lists once every one of them has arrived, and start the game the
+    /// arguments asked for with them. Returns whether any are still on their way.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn poll_word_lists(&mut self) -> bool {
+        let (url, answers, guesses) = match self.word_list_window.download.take() {
+            Some(download) => download,
+            None => return false,
+        };
+        let done = answers.borrow().is_some()
+            && guesses.as_ref().map_or(true, |guesses| guesses.borrow().is_some());
+        if !done {
+            self.word_list_window.download = Some((url, answers, guesses));
+            return true;
+        }
+        let answers = answers.borrow_mut().take().unwrap();
+        let guesses = guesses.map(|guesses| guesses.borrow_mut().take().unwrap()).transpose();
+        match (answers, guesses) {
+            (Ok(answers), Ok(guesses)) => {
+                self.load_word_lists(url, &answers, guesses.as_deref());
+            }
+            (Err(err), _) | (_, Err(err)) => self.show_word_list_errors(url, vec![err]),
+        }
+        if !self.start_from_args() {
+            self.reset_random_word();
+        }
+        false
     }
 
-    true
+    pub fn with_args(args: Args) -> Self {
+        let mut app = Self::default();
+        app.args = args;
+        app
+    }
+}
+
+// fn get_random_word() -> String {
+//     crate::ANSWER_LIST[WyRand::new().generate_range(0_usize..crate::ANSWER_LIST.len())].to_string()
+// }
+pub(crate) fn get_random_word(len: usize) -> String {
+    let list = crate::answer_list(len).unwrap_or(crate::ANSWER_LIST);
+    list.choose(&mut rand::thread_rng()).unwrap().to_string()
 }
 
-#[derive(Default, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Args {
     pub word: Option<String>,
+    pub max_guesses: Option<usize>,
+    pub hard_mode: Option<bool>,
+    /// Where to load custom answers from: a file path natively, or a URL on the web.
+    pub answers: Option<String>,
+    /// Where to load custom accepted guesses from, in the same way as `answers`.
+    pub guesses: Option<String>,
+    /// The code of the language to play in, which is kept for later games too.
+    pub language: Option<String>,
+    /// The creator's message from a challenge link.
+    pub message: Option<String>,
+    /// Why the challenge link couldn't be played, if it couldn't.
+    pub link_error: Option<String>,
+    /// Pick the first answer with a random generator seeded with this, so it's the same each time.
+    pub seed: Option<u64>,
+    /// Start with today's daily puzzle.
+    pub daily: bool,
+    /// The length of the first answer, if not the one in the settings.
+    pub word_len: Option<usize>,
+    /// How many boards the first game is played on, if not the number in the settings.
+    pub boards: Option<usize>,
+    /// Make the first game adversarial, whatever the settings say.
+    pub adversarial: bool,
+    /// The timer and time limit in seconds to play with, which are kept for later games too.
+    pub timing: Option<(Timing, u32)>,
+    /// Where challenge links point, instead of the address in the settings.
+    pub share_url: Option<String>,
 }
 
-pub(crate) fn encode(s: String) -> String {
-    let mut out = s.to_uppercase();
-    for _ in 0..5 {
-        out = base64::encode_config(out, base64::URL_SAFE_NO_PAD);
-    }
-    out
+/// Text that is still being downloaded. It's filled in once the download finishes or fails.
+#[cfg(target_arch = "wasm32")]
+pub(crate) type Download = std::rc::Rc<std::cell::RefCell<Option<Result<String, String>>>>;
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn download(url: &str) -> Download {
+    use eframe::wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    let result = Download::default();
+    let slot = result.clone();
+    let url = url.to_string();
+    wasm_bindgen_futures::spawn_local(async move {
+        let text = async {
+            let window = web_sys::window().expect("no global window");
+            let response: web_sys::Response =
+                JsFuture::from(window.fetch_with_str(&url)).await?.dyn_into()?;
+            if !response.ok() {
+                return Ok(Err(format!("Couldn't download {}: status {}", url, response.status())));
+            }
+            let text = JsFuture::from(response.text()?).await?;
+            Ok::<_, JsValue>(Ok(text.as_string().unwrap_or_default()))
+        }
+        .await;
+        *slot.borrow_mut() =
+            Some(text.unwrap_or_else(|err| Err(format!("Couldn't download {}: {:?}", url, err))));
+    });
+    result
 }
 
-pub(crate) fn decode(s: String) -> Result<String, base64::DecodeError> {
-    let mut out = s;
-    for _ in 0..5 {
-        out =
-            String::from_utf8_lossy(&*base64::decode_config(out, base64::URL_SAFE_NO_PAD)?).into();
-    }
-    Ok(out.to_uppercase())
+/// Save `bytes` as a file called `name`, asking where with a file dialog. Returns what happened,
+/// or `None` if the player changed their mind.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn save_file(name: &str, bytes: &[u8]) -> Option<String> {
+    let extension = name.rsplit('.').next().unwrap_or_default();
+    let path = rfd::FileDialog::new()
+        .set_file_name(name)
+        .add_filter(&extension.to_uppercase(), &[extension])
+        .save_file()?;
+    Some(match std::fs::write(&path, bytes) {
+        Ok(()) => format!("Saved {}", path.display()),
+        Err(err) => format!("Couldn't save {}: {}", path.display(), err),
+    })
 }
 
-pub(crate) fn gen_qrcode(s: String) -> (Vec<qrcode::Color>, usize) {
-    if let Some(base) = get_url_base() {
-        if let Ok(code) = QrCode::new(format!("{}?bword={}", base, encode(s))) {
-            (code.to_colors(), code.width())
-        } else {
-            (Vec::new(), 0)
-        }
+/// Save `bytes` as a file called `name` by having the browser download it. Returns an error if
+/// the download couldn't be started; where it ends up is up to the browser.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn save_file(name: &str, bytes: &[u8]) -> Option<String> {
+    use eframe::wasm_bindgen::{JsCast, JsValue};
+
+    let result = (|| {
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let blob = web_sys::Blob::new_with_u8_array_sequence(&parts)?;
+        let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+        let document = web_sys::window().and_then(|window| window.document());
+        let link: web_sys::HtmlAnchorElement =
+            document.ok_or("no document")?.create_element("a")?.dyn_into()?;
+        link.set_href(&url);
+        link.set_download(name);
+        link.click();
+        web_sys::Url::revoke_object_url(&url)
+    })();
+    result.err().map(|err: JsValue| format!("Couldn't save {}: {:?}", name, err))
+}
+
+pub(crate) fn gen_qrcode(url: &str) -> (Vec<qrcode::Color>, usize) {
+    if let Ok(code) = QrCode::new(url) {
+        (code.to_colors(), code.width())
     } else {
         (Vec::new(), 0)
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-fn get_url_base() -> Option<String> {
-    let loc = &web_sys::window().expect("no global window").location();
-    if let Ok(origin) = loc.origin() {
-        if let Ok(pathname) = loc.pathname() {
-            Some(format!("{}{}", origin, pathname))
+impl WordleApp {
+    /// The link to play `challenge` in the current language, if the game knows where it's
+    /// hosted.
+    pub(crate) fn challenge_link(&self, challenge: &Challenge) -> Option<String> {
+        Some(challenge::link(&self.get_url_base()?, challenge, self.settings.language()))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn get_url_base(&self) -> Option<String> {
+        let loc = &web_sys::window().expect("no global window").location();
+        if let Ok(origin) = loc.origin() {
+            if let Ok(pathname) = loc.pathname() {
+                Some(format!("{}{}", origin, pathname))
+            } else {
+                None
+            }
         } else {
             None
         }
-    } else {
-        None
     }
-}
-#[cfg(not(target_arch = "wasm32"))]
-fn get_url_base() -> Option<String> {
-    Some("https://dacid44.github.io/wordle_clone/".to_string())
+
+    /// The address from the command line or environment, then the one in the settings if it's
+    /// usable, then the public site.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn get_url_base(&self) -> Option<String> {
+        let setting =
+            Some(self.settings.share_url.trim()).filter(|url| challenge::is_link_base(url));
+        let base = self.args.share_url.as_deref().or(setting);
+        Some(base.unwrap_or(challenge::DEFAULT_LINK_BASE).to_string())
+    }
 }
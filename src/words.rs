@@ -0,0 +1,174 @@
+//! The answers and accepted guesses for one word length, either built in or loaded at runtime.
+
+use crate::language::Language;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordList {
+    len: usize,
+    answers: Vec<Cow<'static, str>>,
+    /// Sorted, and always contains every answer.
+    guesses: Vec<Cow<'static, str>>,
+}
+
+/// A problem with one line of a custom word list. Lines are counted from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordListError {
+    Empty,
+    WrongLength { line: usize, word: String, expected: usize },
+    UnsupportedLength { len: usize },
+    InvalidLetter { line: usize, word: String, letter: char },
+    Duplicate { line: usize, word: String },
+}
+
+impl fmt::Display for WordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "The list has no words"),
+            Self::WrongLength { line, word, expected } => write!(
+                f,
+                "Line {}: \"{}\" has {} letters, expected {}",
+                line,
+                word,
+                word.chars().count(),
+                expected
+            ),
+            Self::UnsupportedLength { len } => write!(
+                f,
+                "Words must have {} to {} letters, not {}",
+                crate::MIN_WORD_LEN,
+                crate::MAX_WORD_LEN,
+                len
+            ),
+            Self::InvalidLetter { line, word, letter } => {
+                write!(f, "Line {}: \"{}\" contains '{}'", line, word, letter)
+            }
+            Self::Duplicate { line, word } => {
+                write!(f, "Line {}: \"{}\" is already in the list", line, word)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordListError {}
+
+impl WordList {
+    /// The lists compiled into the game, if it has any for `len` letters.
+    pub fn builtin(len: usize) -> Option<Self> {
+        let answers = crate::answer_list(len)?;
+        let guesses = crate::guess_list(len)?;
+        Some(Self {
+            len,
+            answers: answers.iter().map(|&word| Cow::Borrowed(word)).collect(),
+            guesses: guesses.iter().map(|&word| Cow::Borrowed(word)).collect(),
+        })
+    }
+
+    /// A list that accepts nothing, for lengths the game has no words for.
+    pub fn empty(len: usize) -> Self {
+        Self { len, answers: Vec::new(), guesses: Vec::new() }
+    }
+
+    /// Build a list in `language` from the text of an answer file and, optionally, a guess file,
+    /// with one word per line. Blank lines and lines starting with `#` are skipped. Without a
+    /// guess file, the language's built-in guesses of the same length are accepted as well as the
+    /// answers.
+    pub fn custom(
+        language: &Language,
+        answers: &str,
+        guesses: Option<&str>,
+    ) -> Result<Self, Vec<WordListError>> {
+        let answers = parse_words(language, answers, None)?;
+        let len = answers[0].chars().count();
+        let mut all_guesses: Vec<Cow<'static, str>> = match guesses {
+            Some(guesses) => {
+                parse_words(language, guesses, Some(len))?.into_iter().map(Cow::Owned).collect()
+            }
+            None => language.word_list(len).map_or_else(Vec::new, |list| list.guesses.clone()),
+        };
+        all_guesses.extend(answers.iter().cloned().map(Cow::Owned));
+        all_guesses.sort_unstable();
+        all_guesses.dedup();
+        Ok(Self {
+            len,
+            answers: answers.into_iter().map(Cow::Owned).collect(),
+            guesses: all_guesses,
+        })
+    }
+
+    pub fn word_len(&self) -> usize {
+        self.len
+    }
+
+    pub fn answers(&self) -> &[Cow<'static, str>] {
+        &self.answers
+    }
+
+    pub fn guesses(&self) -> &[Cow<'static, str>] {
+        &self.guesses
+    }
+
+    /// Check an uppercase word against the accepted guesses.
+    pub fn is_guess(&self, word: &str) -> bool {
+        self.guesses.binary_search_by(|guess| (**guess).cmp(word)).is_ok()
+    }
+}
+
+/// Read one word per line, folded into `language`'s letters, checking that they all have the same
+/// supported length (or `len`, if given), use only letters of the language, and appear once.
+fn parse_words(
+    language: &Language,
+    text: &str,
+    len: Option<usize>,
+) -> Result<Vec<String>, Vec<WordListError>> {
+    let mut words = Vec::new();
+    let mut seen = HashSet::new();
+    let mut errors = Vec::new();
+    let mut len = len;
+
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let word = line.trim();
+        if word.is_empty() || word.starts_with('#') {
+            continue;
+        }
+        let word = match language.fold_word(word) {
+            Ok(word) => word,
+            Err(letter) => {
+                let word = word.to_string();
+                errors.push(WordListError::InvalidLetter { line: line_number, word, letter });
+                continue;
+            }
+        };
+        let word_len = word.chars().count();
+        match len {
+            None if !(crate::MIN_WORD_LEN..=crate::MAX_WORD_LEN).contains(&word_len) => {
+                errors.push(WordListError::UnsupportedLength { len: word_len });
+                // Every other line would be reported against this one, so stop here
+                break;
+            }
+            None => len = Some(word_len),
+            Some(expected) if expected != word_len => {
+                errors.push(WordListError::WrongLength { line: line_number, word, expected });
+                continue;
+            }
+            Some(_) => {}
+        }
+        if !seen.insert(word.clone()) {
+            errors.push(WordListError::Duplicate { line: line_number, word });
+            continue;
+        }
+        words.push(word);
+    }
+
+    if words.is_empty() && errors.is_empty() {
+        errors.push(WordListError::Empty);
+    }
+    if errors.is_empty() {
+        Ok(words)
+    } else {
+        Err(errors)
+    }
+}
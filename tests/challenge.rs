@@ -0,0 +1,103 @@
+use wordle_clone::challenge::{
+    is_link_base, link, link_args, Challenge, ChallengeError, DEFAULT_LINK_BASE,
+};
+use wordle_clone::engine::Rules;
+use wordle_clone::language::{ENGLISH, SPANISH};
+
+#[test]
+fn challenges_survive_a_round_trip() {
+    let rules = Rules { max_guesses: 8, hard_mode: true };
+    let challenge = Challenge::new("CAÑÓN", rules).with_message("  Good luck! ¡Suerte!  ");
+    let code = challenge.encode();
+    assert!(!code.contains("CA"), "the word shouldn't be readable in {}", code);
+
+    let decoded = Challenge::decode(&code).unwrap();
+    assert_eq!(decoded, challenge);
+    assert_eq!(decoded.message.as_deref(), Some("Good luck! ¡Suerte!"));
+
+    let plain = Challenge::new("CRANE", Rules::default());
+    assert_eq!(Challenge::decode(&plain.encode()).unwrap(), plain);
+}
+
+#[test]
+fn corrupt_links_are_rejected() {
+    let code = Challenge::new("CRANE", Rules::default()).with_message("Hi").encode();
+
+    // Change one character of the payload
+    let mut changed: Vec<char> = code.chars().collect();
+    changed[6] = if changed[6] == 'A' { 'B' } else { 'A' };
+    let changed: String = changed.into_iter().collect();
+    assert_eq!(Challenge::decode(&changed), Err(ChallengeError::Checksum));
+
+    assert_eq!(Challenge::decode(&code[..code.len() - 4]), Err(ChallengeError::Checksum));
+    assert_eq!(Challenge::decode("AAA"), Err(ChallengeError::Truncated));
+    assert_eq!(Challenge::decode("not a code!"), Err(ChallengeError::NotBase64));
+    assert_eq!(
+        ChallengeError::Checksum.to_string(),
+        "This challenge link is damaged: its checksum is wrong"
+    );
+
+    // Made by hand, or by a game with other limits
+    for max_guesses in [0, 1, 13, 255] {
+        let code = Challenge::new("CRANE", Rules { max_guesses, hard_mode: false }).encode();
+        assert_eq!(Challenge::decode(&code), Err(ChallengeError::GuessLimit(max_guesses)));
+    }
+    let args = link_args(|name| (name == "guesses").then(|| "40".to_string()));
+    assert_eq!(args.max_guesses, None);
+}
+
+#[test]
+fn old_bword_links_still_work() {
+    let mut code = "CRANE".to_string();
+    for _ in 0..5 {
+        code = base64::encode_config(code, base64::URL_SAFE_NO_PAD);
+    }
+    let challenge = Challenge::decode_legacy(&code).unwrap();
+    assert_eq!(challenge, Challenge::new("CRANE", Rules::default()));
+}
+
+/// Look a parameter up in the query of `url`, as the browser does for the web version.
+fn query_param(url: &str, name: &str) -> Option<String> {
+    let query = url.split_once('?')?.1;
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == name).then(|| value.to_string())
+    })
+}
+
+#[test]
+fn links_are_read_back_as_they_were_made() {
+    let rules = Rules { max_guesses: 5, hard_mode: true };
+    let challenge = Challenge::new("CAÑON", rules).with_message("¡Hola!");
+    let url = link("https://games.example.com/wordle/?team=blue", &challenge, &SPANISH);
+    assert!(url.starts_with("https://games.example.com/wordle/?team=blue&c="));
+
+    let args = link_args(|name| query_param(&url, name));
+    assert_eq!(args.word.as_deref(), Some("CAÑON"));
+    assert_eq!((args.max_guesses, args.hard_mode), (Some(5), Some(true)));
+    assert_eq!(args.language.as_deref(), Some("es"));
+    assert_eq!(args.message.as_deref(), Some("¡Hola!"));
+    assert_eq!(args.link_error, None);
+
+    // The payload's rules can't be changed by adding the old parameters to the link
+    let tampered = format!("{}&guesses=10&hard=0", url);
+    let args = link_args(|name| query_param(&tampered, name));
+    assert_eq!((args.max_guesses, args.hard_mode), (Some(5), Some(true)));
+
+    // The word has to be one the language knows
+    let url = link(DEFAULT_LINK_BASE, &Challenge::new("CAÑON", Rules::default()), &ENGLISH);
+    let args = link_args(|name| query_param(&url, name));
+    assert_eq!(args.word, None);
+    assert_eq!(
+        args.link_error.as_deref(),
+        Some("The challenge word isn't in the English word list")
+    );
+
+    // Unless the link brings word lists of its own, which the game checks once they're fetched
+    let url = link(DEFAULT_LINK_BASE, &Challenge::new("XYZZY", Rules::default()), &ENGLISH);
+    let url = format!("{}&answer_list=https://example.com/words.txt", url);
+    let args = link_args(|name| query_param(&url, name));
+    assert_eq!(args.word.as_deref(), Some("XYZZY"));
+    assert_eq!(args.link_error, None);
+    assert!(!is_link_base("ftp://example.com/") && is_link_base(DEFAULT_LINK_BASE));
+}
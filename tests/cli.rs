@@ -0,0 +1,98 @@
+use wordle_clone::challenge::{Challenge, ChallengeError};
+use wordle_clone::cli::{parse, CliError};
+use wordle_clone::engine::Rules;
+use wordle_clone::timer::Timing;
+
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn options_are_passed_on_to_the_game() {
+    let options = parse(args("--tui --hard --max-guesses=8 --lang es cañón")).unwrap();
+    assert!(options.tui && !options.help);
+    assert_eq!(options.args.word.as_deref(), Some("CAÑON"));
+    assert_eq!(options.args.max_guesses, Some(8));
+    assert_eq!(options.args.hard_mode, Some(true));
+    assert_eq!(options.args.language.as_deref(), Some("es"));
+
+    let options = parse(args("--seed 42 --length 7 --answers a.txt --guesses g.txt")).unwrap();
+    assert_eq!((options.args.seed, options.args.word_len), (Some(42), Some(7)));
+    assert_eq!(options.args.answers.as_deref(), Some("a.txt"));
+    assert_eq!(options.args.guesses.as_deref(), Some("g.txt"));
+    assert_eq!(parse(args("--boards 4")).unwrap().args.boards, Some(4));
+    assert!(parse(args("--absurdle --hard")).unwrap().args.adversarial);
+    let options = parse(args("--speedrun 300")).unwrap();
+    assert_eq!(options.args.timing, Some((Timing::Speedrun, 300)));
+
+    assert!(parse(args("--daily --hard")).unwrap().args.daily);
+    let options = parse(args("--share-url https://wordle.example.com/")).unwrap();
+    assert_eq!(options.args.share_url.as_deref(), Some("https://wordle.example.com/"));
+    assert!(parse(args("--word crane --help")).unwrap().help);
+}
+
+#[test]
+fn challenge_codes_bring_their_rules() {
+    let rules = Rules { max_guesses: 4, hard_mode: true };
+    let code = Challenge::new("CRANE", rules).with_message("Hi!").encode();
+    let options = parse(vec!["--bword".to_string(), code]).unwrap();
+    assert_eq!(options.args.word.as_deref(), Some("CRANE"));
+    assert_eq!((options.args.max_guesses, options.args.hard_mode), (Some(4), Some(true)));
+    assert_eq!(options.args.message.as_deref(), Some("Hi!"));
+
+    // Old links held only the word
+    let mut code = "crane".to_string();
+    for _ in 0..5 {
+        code = base64::encode_config(code, base64::URL_SAFE_NO_PAD);
+    }
+    let options = parse(vec!["--bword".to_string(), code]).unwrap();
+    assert_eq!(options.args.word.as_deref(), Some("CRANE"));
+}
+
+#[test]
+fn bad_options_are_explained() {
+    let error = |line: &str| parse(args(line)).unwrap_err();
+    assert_eq!(error("--frob"), CliError::UnknownOption("--frob".to_string()));
+    assert_eq!(error("--seed"), CliError::MissingValue("--seed"));
+    assert_eq!(error("--word qzqzq").to_string(), "\"qzqzq\" isn't in the English word list");
+    assert_eq!(
+        error("--max-guesses 40").to_string(),
+        "Invalid value \"40\" for --max-guesses: expected a number from 2 to 12"
+    );
+    assert_eq!(error("--daily crane"), CliError::Conflict("--daily", "a word"));
+    assert_eq!(
+        error("--boards 3").to_string(),
+        "Invalid value \"3\" for --boards: expected 1, 2, 4 or 8"
+    );
+    assert_eq!(error("--boards 2 crane"), CliError::Conflict("--boards", "a word"));
+    assert_eq!(error("--absurdle --boards 2"), CliError::Conflict("--absurdle", "--boards"));
+    assert_eq!(
+        error("--countdown 60 --speedrun 60"),
+        CliError::Conflict("--countdown", "--speedrun")
+    );
+    assert_eq!(error("--speedrun 60 crane"), CliError::Conflict("--speedrun", "a word"));
+    assert_eq!(error("--guesses g.txt"), CliError::Requires("--guesses", "--answers"));
+    assert_eq!(error("--bword not-a-code"), CliError::BadCode(ChallengeError::Checksum));
+    assert_eq!(error("crane slate"), CliError::ExtraArgument("slate".to_string()));
+    assert_eq!(
+        error("--share-url intranet/wordle").to_string(),
+        "Invalid value \"intranet/wordle\" for --share-url: expected an http:// or https:// address"
+    );
+}
+
+#[test]
+fn words_are_checked_against_custom_answers() {
+    let path = std::env::temp_dir().join("wordle_clone_cli_answers.txt");
+    std::fs::write(&path, "zorps\nblick\n").unwrap();
+    let answers = path.display().to_string();
+
+    let options = parse(args(&format!("--answers {} zorps", answers))).unwrap();
+    assert_eq!(options.args.word.as_deref(), Some("ZORPS"));
+    // Without a guess list the built-in guesses are accepted too
+    assert!(parse(args(&format!("--answers {} crane", answers))).is_ok());
+    assert_eq!(
+        parse(args(&format!("--answers {} qxqxq", answers))).unwrap_err(),
+        CliError::NotAWord { word: "qxqxq".to_string(), language: "English" }
+    );
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,29 @@
+use chrono::NaiveDate;
+use wordle_clone::daily::{daily_word, puzzle_number};
+use wordle_clone::ANSWER_LIST;
+
+fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+#[test]
+fn puzzles_are_numbered_by_day() {
+    assert_eq!(puzzle_number(date(2022, 1, 1)), 1);
+    assert_eq!(puzzle_number(date(2022, 1, 2)), 2);
+    assert_eq!(puzzle_number(date(2023, 1, 1)), 366);
+    assert_eq!(puzzle_number(date(2021, 6, 1)), 1);
+}
+
+#[test]
+fn daily_word_is_stable() {
+    // Everyone gets the same answer on the same day, in every release with the same answer list
+    assert_eq!(daily_word(2, ANSWER_LIST), "TENOR");
+    assert_eq!(daily_word(100, ANSWER_LIST), "MIXER");
+    assert_eq!(daily_word(365, ANSWER_LIST), "RUMOR");
+    for number in 1..100 {
+        assert!(ANSWER_LIST.contains(&daily_word(number, ANSWER_LIST)));
+    }
+    // Neighbouring days shouldn't share an answer
+    let words: Vec<&str> = (1..30).map(|n| daily_word(n, ANSWER_LIST)).collect();
+    assert!(words.windows(2).all(|pair| pair[0] != pair[1]));
+}
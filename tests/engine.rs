@@ -0,0 +1,268 @@
+use std::sync::Arc;
+use wordle_clone::engine::{
+    hardest_pattern, scaled_max_guesses, score_guess, Game, GameState, GuessError, MultiGame,
+    Rules, DEFAULT_MAX_GUESSES,
+};
+use wordle_clone::language::ENGLISH;
+use wordle_clone::words::WordList;
+use wordle_clone::{answer_list, guess_list, is_word, MAX_WORD_LEN, MIN_WORD_LEN};
+
+#[test]
+fn every_supported_length_has_words() {
+    for len in MIN_WORD_LEN..=MAX_WORD_LEN {
+        let answers = answer_list(len).unwrap();
+        let guesses = guess_list(len).unwrap();
+        assert!(!answers.is_empty(), "no {}-letter answers", len);
+        assert!(answers.len() < guesses.len());
+        assert!(guesses.iter().all(|word| word.chars().count() == len && is_word(word)));
+        assert!(answers.iter().all(|word| is_word(word)));
+    }
+    assert!(guess_list(MIN_WORD_LEN - 1).is_none());
+    assert!(guess_list(MAX_WORD_LEN + 1).is_none());
+}
+
+#[test]
+fn longer_words_are_played_the_same_way() {
+    let mut game = Game::new("ballots");
+    assert_eq!(game.word_len(), 7);
+    assert_eq!(game.guess("crane").unwrap_err(), GuessError::WrongLength { expected: 7, found: 5 });
+    assert_eq!(game.guess("qqqqqqq").unwrap_err(), GuessError::NotInWordList);
+    assert!(game.history().is_empty());
+
+    game.guess("balloon").unwrap();
+    game.guess("ballots").unwrap();
+    assert_eq!(game.state(), &GameState::Success(2));
+    assert_eq!(game.guess("balloon").unwrap_err(), GuessError::GameOver);
+}
+
+#[test]
+fn running_out_of_guesses_fails() {
+    let mut game = Game::new("TRAY");
+    for _ in 0..DEFAULT_MAX_GUESSES {
+        assert_eq!(game.state(), &GameState::Playing);
+        game.guess("STOP").unwrap();
+    }
+    assert_eq!(game.state(), &GameState::Failure);
+    assert_eq!(game.history().len(), DEFAULT_MAX_GUESSES);
+}
+
+#[test]
+fn guess_limit_comes_from_the_rules() {
+    let mut game = Game::with_rules("TRAY", Rules { max_guesses: 8, ..Rules::default() });
+    for _ in 0..7 {
+        game.guess("STOP").unwrap();
+    }
+    assert_eq!(game.state(), &GameState::Playing);
+    game.guess("TRAY").unwrap();
+    assert_eq!(game.state(), &GameState::Success(8));
+
+    let mut game = Game::with_rules("TRAY", Rules { max_guesses: 4, ..Rules::default() });
+    for _ in 0..4 {
+        game.guess("STOP").unwrap();
+    }
+    assert_eq!(game.state(), &GameState::Failure);
+}
+
+#[test]
+fn hard_mode_requires_revealed_hints() {
+    let rules = Rules { hard_mode: true, ..Rules::default() };
+    let mut game = Game::with_rules("CRANE", rules);
+    // C green, R yellow
+    game.guess("CORDS").unwrap();
+
+    assert_eq!(
+        game.guess("BRAND").unwrap_err(),
+        GuessError::MissingGreen { letter: 'C', position: 0 }
+    );
+    assert_eq!(
+        game.guess("CLOTH").unwrap_err(),
+        GuessError::MissingLetter { letter: 'R', count: 1 }
+    );
+    assert_eq!(game.history().len(), 1);
+
+    game.guess("CARTS").unwrap();
+    game.guess("CRANE").unwrap();
+    assert_eq!(game.state(), &GameState::Success(3));
+}
+
+#[test]
+fn hard_mode_counts_repeated_letters() {
+    let rules = Rules { hard_mode: true, ..Rules::default() };
+    let mut game = Game::with_rules("LEVEL", rules);
+    // Both Ls are revealed, one green and one yellow
+    game.guess("LOWLY").unwrap();
+    assert_eq!(
+        game.guess("LEAPT").unwrap_err(),
+        GuessError::MissingLetter { letter: 'L', count: 2 }
+    );
+    assert!(game.guess("LABEL").is_ok());
+}
+
+#[test]
+fn normal_mode_ignores_hints() {
+    let mut game = Game::new("CRANE");
+    game.guess("CORDS").unwrap();
+    assert!(game.guess("BLIMP").is_ok());
+}
+
+#[test]
+fn saved_games_are_replayed() {
+    let rules = Rules { hard_mode: true, ..Rules::default() };
+    let mut game = Game::with_rules("CRANE", rules);
+    game.guess("CORDS").unwrap();
+    game.guess("CARTS").unwrap();
+
+    let saved = game.save();
+    assert_eq!(saved.guesses, vec!["CORDS", "CARTS"]);
+    let restored = Game::restore(&saved).unwrap();
+    assert_eq!(restored.history(), game.history());
+    assert_eq!(restored.rules(), game.rules());
+    assert_eq!(restored.state(), &GameState::Playing);
+
+    // A guess that the rules would reject can't be smuggled in through a save
+    let mut tampered = saved;
+    tampered.guesses.push("BLIMP".to_string());
+    assert_eq!(
+        Game::restore(&tampered).unwrap_err(),
+        GuessError::MissingGreen { letter: 'C', position: 0 }
+    );
+}
+
+#[test]
+fn inflected_words_are_guesses_but_not_answers() {
+    for word in ["CATS", "BAKED", "BOXES", "CRIES", "STOPPED", "MAKING"] {
+        assert!(is_word(word), "{} should be a guess", word);
+        let answers = answer_list(word.len()).unwrap();
+        assert!(answers.binary_search(&word).is_err(), "{} shouldn't be an answer", word);
+    }
+    assert!(answer_list(5).unwrap().binary_search(&"CRANE").is_ok());
+}
+
+#[test]
+fn words_that_only_look_inflected_are_answers() {
+    for word in ["HEARD", "NEED", "BOARD", "CROWD", "WEIRD", "SEED", "THING", "KING"] {
+        let answers = answer_list(word.len()).unwrap();
+        assert!(answers.binary_search(&word).is_ok(), "{} should be an answer", word);
+    }
+}
+
+#[test]
+fn rejected_guesses_leave_the_game_alone() {
+    let mut game = Game::new("CRANE");
+    let error = game.guess("CRA").unwrap_err();
+    assert_eq!(error.to_string(), "Not enough letters");
+    assert_eq!(game.guess("CRANES").unwrap_err().to_string(), "Too many letters");
+    assert_eq!(game.guess("XXXXX").unwrap_err().to_string(), "Not in word list");
+    assert!(game.history().is_empty());
+    assert_eq!(game.state(), &GameState::Playing);
+}
+
+fn multi_game(answers: &[&str], rules: Rules) -> MultiGame {
+    MultiGame::new(answers, rules, Arc::new(WordList::builtin(5).unwrap()))
+}
+
+#[test]
+fn solved_boards_lock() {
+    let rules =
+        Rules { max_guesses: scaled_max_guesses(DEFAULT_MAX_GUESSES, 2), ..Rules::default() };
+    let mut game = multi_game(&["CRANE", "SLOTH"], rules);
+    game.guess("CRANE").unwrap();
+    assert_eq!(game.state(), &GameState::Playing);
+    assert_eq!(game.boards()[0].state(), &GameState::Success(1));
+
+    game.guess("STOMP").unwrap();
+    game.guess("SLOTH").unwrap();
+    assert_eq!(game.boards()[0].history().len(), 1);
+    assert_eq!(game.boards()[1].state(), &GameState::Success(3));
+    assert_eq!(game.state(), &GameState::Success(3));
+    assert_eq!(game.guesses(), ["CRANE", "STOMP", "SLOTH"]);
+    assert_eq!(game.guess("CRANE").unwrap_err(), GuessError::GameOver);
+}
+
+#[test]
+fn any_unsolved_board_loses() {
+    let mut game = multi_game(&["CRANE", "SLOTH"], Rules { max_guesses: 2, ..Rules::default() });
+    game.guess("CRANE").unwrap();
+    game.guess("STOMP").unwrap();
+    assert_eq!(game.state(), &GameState::Failure);
+    assert_eq!(scaled_max_guesses(6, 1), 6);
+    assert_eq!(scaled_max_guesses(6, 8), 13);
+}
+
+#[test]
+fn hard_mode_applies_to_every_playing_board() {
+    let rules = Rules { hard_mode: true, ..Rules::default() };
+    let mut game = multi_game(&["CRANE", "SLOTH"], rules);
+    // Fine for the first board, but the second has a yellow O and S
+    game.guess("CORDS").unwrap();
+    assert!(matches!(game.guess("CRANE"), Err(GuessError::MissingLetter { count: 1, .. })));
+    assert_eq!(game.guesses().len(), 1);
+}
+
+#[test]
+fn saved_multi_games_are_replayed() {
+    let words = Arc::new(WordList::builtin(5).unwrap());
+    let mut game = MultiGame::new(&["CRANE", "SLOTH", "TRAYS", "PLUMB"], Rules::default(), words);
+    game.guess("CRANE").unwrap();
+    game.guess("STOMP").unwrap();
+    let saved = game.save();
+    assert_eq!(saved.others, ["SLOTH", "TRAYS", "PLUMB"]);
+
+    let restored = MultiGame::restore_with_words(&saved, game.words().clone()).unwrap();
+    assert_eq!(restored.answers(), game.answers());
+    assert_eq!(restored.guesses(), game.guesses());
+    assert_eq!(restored.boards()[0].history().len(), 1);
+    assert_eq!(restored.boards()[3].history().len(), 2);
+}
+
+#[test]
+fn adversarial_games_keep_the_most_candidates() {
+    let words = WordList::custom(&ENGLISH, "CRANE\nCRATE\nGRATE\nSLOTH", None).unwrap();
+    let mut game = Game::adversarial(Rules::default(), Arc::new(words));
+    assert_eq!(game.candidates().unwrap().len(), 4);
+
+    // Guessing SLOTH splits them into SLOTH, CRATE and GRATE (yellow T), and CRANE (all gray)
+    let guess = game.guess("SLOTH").unwrap().clone();
+    assert_eq!(guess.pattern, hardest_pattern("SLOTH", &["CRATE", "GRATE"]));
+    assert_eq!(game.candidates().unwrap(), ["CRATE", "GRATE"]);
+    assert_eq!(game.state(), &GameState::Playing);
+
+    // A correct guess is only scored as one when nothing else is left
+    assert!(!game.guess("CRATE").unwrap().pattern.is_solved());
+    assert_eq!(game.candidates().unwrap(), ["GRATE"]);
+    assert_eq!(game.answer(), "GRATE");
+    game.guess("GRATE").unwrap();
+    assert_eq!(game.state(), &GameState::Success(3));
+}
+
+#[test]
+fn adversarial_ties_reveal_the_least() {
+    let pattern = hardest_pattern("SLOTH", &["CRANE", "CRATE", "SLOTH"]);
+    assert_eq!(pattern, score_guess("SLOTH", "CRANE"));
+    assert!(hardest_pattern("CRANE", &["CRANE"]).is_solved());
+}
+
+#[test]
+fn saved_adversarial_games_are_replayed() {
+    let words = Arc::new(WordList::builtin(5).unwrap());
+    let mut game = Game::adversarial(Rules::default(), words.clone());
+    game.guess("CRANE").unwrap();
+    game.guess("SLOTH").unwrap();
+    let saved = game.save();
+    assert!(saved.adversarial);
+
+    let restored = MultiGame::restore_with_words(&saved, words).unwrap();
+    assert_eq!(restored.boards()[0].candidates(), game.candidates());
+    assert_eq!(restored.boards()[0].history(), game.history());
+}
+
+#[test]
+fn resigning_loses_the_unsolved_boards() {
+    let mut game = multi_game(&["CRANE", "SLOTH"], Rules::default());
+    game.guess("CRANE").unwrap();
+    game.resign();
+    assert_eq!(game.state(), &GameState::Failure);
+    assert_eq!(game.boards()[0].state(), &GameState::Success(1));
+    assert_eq!(game.boards()[1].state(), &GameState::Failure);
+    assert_eq!(game.guess("SLOTH").unwrap_err(), GuessError::GameOver);
+}
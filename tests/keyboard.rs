@@ -0,0 +1,29 @@
+use wordle_clone::keyboard::KeyboardLayout;
+use wordle_clone::language::{GERMAN, LANGUAGES, SPANISH};
+
+#[test]
+fn every_layout_has_every_letter_once() {
+    for language in LANGUAGES {
+        let mut alphabet: Vec<char> = language.alphabet.chars().collect();
+        alphabet.sort_unstable();
+        for layout in KeyboardLayout::ALL {
+            let mut keys: Vec<char> = layout.rows(language).concat();
+            keys.sort_unstable();
+            assert_eq!(keys, alphabet, "{} on {}", language.name, layout.name());
+        }
+    }
+}
+
+#[test]
+fn missing_letters_join_the_end_of_their_row() {
+    let rows = KeyboardLayout::Azerty.rows(&SPANISH);
+    assert_eq!(rows[0].iter().collect::<String>(), "AZERTYUIOP");
+    assert_eq!(rows[1].iter().collect::<String>(), "QSDFGHJKLMÑ");
+    assert_eq!(rows[2].iter().collect::<String>(), "WXCVBN");
+
+    let rows = KeyboardLayout::Dvorak.rows(&GERMAN);
+    assert_eq!(rows[0].iter().collect::<String>(), "PYFGCRLÜ");
+    assert_eq!(rows[1].iter().collect::<String>(), "AOEUIDHTNSÖÄ");
+
+    assert_eq!(KeyboardLayout::Language.rows(&GERMAN)[0].iter().collect::<String>(), "QWERTZUIOPÜ");
+}
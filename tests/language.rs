@@ -0,0 +1,71 @@
+use std::sync::Arc;
+use wordle_clone::engine::{Game, GuessError, Rules};
+use wordle_clone::language::{Language, ENGLISH, FINNISH, GERMAN, LANGUAGES, SPANISH};
+
+#[test]
+fn every_language_has_valid_built_in_words() {
+    for language in LANGUAGES {
+        assert_eq!(Language::from_code(language.code).unwrap().name, language.name);
+        let lens = language.word_lens();
+        for len in lens.clone() {
+            let words = language.word_list(len).unwrap_or_else(|| panic!("{}", language.name));
+            assert!(!words.answers().is_empty());
+        }
+        assert!(language.word_list(lens.end() + 1).is_none());
+        // Every letter of the alphabet is on the keyboard, once
+        let mut keys: Vec<char> = language.keyboard.concat().chars().collect();
+        let mut alphabet: Vec<char> = language.alphabet.chars().collect();
+        keys.sort_unstable();
+        alphabet.sort_unstable();
+        assert_eq!(keys, alphabet, "{}", language.name);
+    }
+}
+
+#[test]
+fn letters_are_folded_into_the_alphabet() {
+    assert_eq!(ENGLISH.fold('q'), Some('Q'));
+    assert_eq!(ENGLISH.fold('ñ'), None);
+    assert_eq!(ENGLISH.fold('1'), None);
+
+    assert_eq!(SPANISH.fold('ñ'), Some('Ñ'));
+    assert_eq!(SPANISH.fold('á'), Some('A'));
+    assert_eq!(SPANISH.fold_word("árbol"), Ok("ARBOL".to_string()));
+    assert_eq!(SPANISH.fold_word("señor"), Ok("SEÑOR".to_string()));
+
+    assert_eq!(GERMAN.fold('ü'), Some('Ü'));
+    // ß uppercases to two letters
+    assert_eq!(GERMAN.fold_word("straße"), Err('ß'));
+    assert_eq!(FINNISH.fold_word("pöytä"), Ok("PÖYTÄ".to_string()));
+}
+
+#[test]
+fn words_with_local_letters_can_be_guessed() {
+    let words = SPANISH.word_list(5).unwrap();
+    assert!(words.is_guess("SEÑOR") && words.is_guess("ARBOL"));
+    assert!(!words.is_guess("ÁRBOL"));
+    assert!(GERMAN.word_list(5).unwrap().is_guess("KÖNIG"));
+}
+
+#[test]
+fn games_take_guesses_already_folded() {
+    let game = Game::with_words("SEÑOR", Rules::default(), SPANISH.word_list(5).unwrap());
+    assert_eq!(game.check("señor"), Ok("SEÑOR".to_string()));
+    assert_eq!(game.check("árbol"), Err(GuessError::NotInWordList));
+    assert_eq!(game.check(&SPANISH.fold_word("árbol").unwrap()), Ok("ARBOL".to_string()));
+}
+
+#[test]
+fn more_words_are_accepted_than_picked() {
+    for language in LANGUAGES {
+        // Read once and shared after that
+        let words = language.word_list(5).unwrap();
+        assert!(Arc::ptr_eq(&words, &language.word_list(5).unwrap()));
+    }
+    for language in [&SPANISH, &GERMAN, &FINNISH] {
+        let words = language.word_list(5).unwrap();
+        assert!(words.guesses().len() > words.answers().len() * 5, "{}", language.name);
+    }
+    assert!(SPANISH.word_list(5).unwrap().is_guess("SACAR"));
+    assert!(GERMAN.word_list(5).unwrap().is_guess("MACHT"));
+    assert!(FINNISH.word_list(5).unwrap().is_guess("KYLMÄ"));
+}
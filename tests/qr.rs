@@ -0,0 +1,35 @@
+use qrcode::{Color, QrCode};
+use wordle_clone::qr::{to_png, to_svg, PNG_SCALE};
+
+fn code() -> (Vec<Color>, usize) {
+    let code = QrCode::new("https://example.com/?c=AQACBQY").unwrap();
+    (code.to_colors(), code.width())
+}
+
+#[test]
+fn pngs_have_a_border_and_scaled_modules() {
+    let (data, width) = code();
+    let png = to_png(&data, width);
+    let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).unwrap();
+    let size = (width + 8) * PNG_SCALE;
+    assert_eq!((info.width as usize, info.height as usize), (size, size));
+
+    // The corners are blank, and the first module of the code is a dark finder pattern
+    assert_eq!(pixels[0], 255);
+    assert_eq!(pixels[4 * PNG_SCALE * size + 4 * PNG_SCALE], 0);
+    let dark = pixels.iter().filter(|&&pixel| pixel == 0).count();
+    let modules = data.iter().filter(|&&color| color == Color::Dark).count();
+    assert_eq!(dark, modules * PNG_SCALE * PNG_SCALE);
+}
+
+#[test]
+fn svgs_draw_every_dark_module() {
+    let (data, width) = code();
+    let svg = to_svg(&data, width);
+    assert!(svg.contains(&format!("viewBox=\"0 0 {0} {0}\"", width + 8)));
+    let modules = data.iter().filter(|&&color| color == Color::Dark).count();
+    assert_eq!(svg.matches("h1v1h-1z").count(), modules);
+    assert!(svg.contains("M4 4h1v1h-1z"));
+}
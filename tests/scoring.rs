@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use wordle_clone::engine::{score_guess, CellState, Pattern};
+use wordle_clone::GUESS_LIST;
+
+/// Write a pattern the way players read it: G for green, Y for yellow, . for gray.
+fn show(pattern: Pattern) -> String {
+    pattern
+        .states()
+        .iter()
+        .map(|state| match state {
+            CellState::Green => 'G',
+            CellState::Yellow => 'Y',
+            _ => '.',
+        })
+        .collect()
+}
+
+fn check(guess: &str, answer: &str, expected: &str) {
+    assert_eq!(show(score_guess(guess, answer)), expected, "{} against {}", guess, answer);
+}
+
+#[test]
+fn distinct_letters() {
+    check("CRANE", "CRANE", "GGGGG");
+    check("CRANE", "TOILS", ".....");
+    check("CRANE", "NACRE", "YYYYG");
+    check("SLATE", "CRANE", "..G.G");
+}
+
+#[test]
+fn later_green_does_not_use_up_a_yellow() {
+    // Each answer has a second copy of the letter that is green elsewhere in the guess, so the
+    // other copy in the guess still has to be yellow
+    check("GUAVA", "ABACK", "..G.Y");
+    check("SCULL", "BELLE", "...GY");
+    check("JINNS", "NINTH", ".GGY.");
+    check("ZIPPY", "POPES", "..GY.");
+}
+
+#[test]
+fn green_takes_priority_over_earlier_yellow() {
+    // A green copy uses up its answer letter before any yellows are handed out
+    check("EERIE", "THEME", "Y...G");
+    check("SASSY", "GLASS", "YY.G.");
+    check("TOOTH", "TROTS", "G.GG.");
+    check("GEESE", "THOSE", "...GG");
+}
+
+#[test]
+fn repeated_guess_letter_single_answer_letter() {
+    check("EERIE", "CRANE", "..Y.G");
+    check("SPEED", "ABIDE", "..Y.Y");
+    check("ERROR", "ROBOT", ".Y.G.");
+}
+
+#[test]
+fn repeated_answer_letter_single_guess_letter() {
+    check("CRANE", "EERIE", ".Y..G");
+    check("STEAL", "SPEED", "G.G..");
+    check("ROBOT", "FLOOR", "YY.G.");
+}
+
+#[test]
+fn yellows_are_assigned_left_to_right() {
+    check("LEVEL", "HELLO", "YG..Y");
+    check("ALLOT", "HELLO", ".YGY.");
+    check("LLAMA", "HELLO", "YY...");
+}
+
+#[test]
+fn pattern_round_trips_through_states() {
+    let states =
+        [CellState::Green, CellState::Gray, CellState::Yellow, CellState::Yellow, CellState::Gray];
+    let pattern = Pattern::from_states(&states);
+    assert_eq!(pattern.states(), states);
+    assert_eq!(pattern.len(), 5);
+    assert!(!pattern.is_solved());
+    assert!(Pattern::solved(5).is_solved());
+    assert_eq!(Pattern::solved(5), score_guess("HELLO", "HELLO"));
+}
+
+fn letter_counts(word: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for c in word.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Check every rule a scored guess has to satisfy, for a spread of pairs from the word list.
+#[test]
+fn word_list_invariants() {
+    let words: Vec<&str> = GUESS_LIST.iter().copied().step_by(23).collect();
+    for &answer in words.iter().step_by(7) {
+        let answer_counts = letter_counts(answer);
+        for &guess in &words {
+            let states = score_guess(guess, answer).states();
+            let pairs: Vec<(char, char)> = guess.chars().zip(answer.chars()).collect();
+
+            // Green exactly where the letters agree
+            for (&(g, a), state) in pairs.iter().zip(&states) {
+                assert_eq!(g == a, *state == CellState::Green, "{} against {}", guess, answer);
+            }
+
+            // Each letter is colored min(guess count, answer count) times, and the colored
+            // copies that aren't green are the leftmost ones
+            for (letter, &guess_count) in &letter_counts(guess) {
+                let allowed = guess_count.min(*answer_counts.get(letter).unwrap_or(&0));
+                let colored = states
+                    .iter()
+                    .zip(guess.chars())
+                    .filter(|(state, c)| c == letter && **state != CellState::Gray)
+                    .count();
+                assert_eq!(colored, allowed, "{} against {}", guess, answer);
+
+                let mut seen_gray = false;
+                for (state, _) in states.iter().zip(guess.chars()).filter(|(_, c)| c == letter) {
+                    match state {
+                        CellState::Gray => seen_gray = true,
+                        CellState::Yellow => {
+                            assert!(!seen_gray, "{} against {}", guess, answer)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        assert!(score_guess(answer, answer).is_solved());
+    }
+}
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use wordle_clone::engine::{Game, MultiGame, Rules};
+use wordle_clone::share::{
+    multi_share_text, share_text, speedrun_text, timed_share_text, timing_text,
+};
+use wordle_clone::theme::Theme;
+use wordle_clone::timer::{Timer, Timing};
+use wordle_clone::words::WordList;
+
+#[test]
+fn won_game() {
+    let mut game = Game::new("CRANE");
+    game.guess("SLATE").unwrap();
+    game.guess("CRANE").unwrap();
+    assert_eq!(
+        share_text("Wordle Clone #7", &game, Theme::Classic, true),
+        "Wordle Clone #7 2/6\n\n⬛⬛🟩⬛🟩\n🟩🟩🟩🟩🟩"
+    );
+}
+
+#[test]
+fn lost_hard_mode_game() {
+    let rules = Rules { max_guesses: 2, hard_mode: true };
+    let mut game = Game::with_rules("CRANE", rules);
+    game.guess("NACRE").unwrap();
+    game.guess("NACRE").unwrap();
+    let text = share_text("Wordle Clone", &game, Theme::Classic, true);
+    assert!(text.starts_with("Wordle Clone X/2*\n"));
+    assert!(!text.contains("CRANE"));
+}
+
+#[test]
+fn themes_change_the_squares() {
+    let mut game = Game::new("CRANE");
+    game.guess("NACRE").unwrap();
+    assert!(share_text("", &game, Theme::HighContrast, true).ends_with("\n🟦🟦🟦🟦🟧"));
+    assert!(share_text("", &game, Theme::Classic, false).ends_with("\n🟨🟨🟨🟨🟩"));
+    game.guess("SLOTH").unwrap();
+    assert!(share_text("", &game, Theme::Classic, false).ends_with("\n⬜⬜⬜⬜⬜"));
+}
+
+#[test]
+fn multi_board_games_share_guess_counts() {
+    let words = Arc::new(WordList::builtin(5).unwrap());
+    let answers = ["CRANE", "SLOTH", "TRAYS", "PLUMB", "CORDS", "STOMP", "NACRE", "BRAND"];
+    let mut game = MultiGame::new(&answers, Rules { max_guesses: 7, hard_mode: false }, words);
+    for word in ["STOMP", "CRANE", "SLOTH", "TRAYS", "PLUMB", "CORDS", "NACRE"] {
+        game.guess(word).unwrap();
+    }
+    assert_eq!(multi_share_text("Wordle Clone", &game), "Wordle Clone X/7\n\n2️⃣3️⃣4️⃣5️⃣\n6️⃣1️⃣7️⃣🟥");
+}
+
+#[test]
+fn timed_games_share_their_splits() {
+    assert_eq!(timing_text(&[12.5, 30.0, 41.0]), "⏱️ 1:23 (12.5s 30.0s 41.0s)");
+    assert_eq!(
+        speedrun_text("Wordle Clone", 7, 300.0),
+        "Wordle Clone speedrun\n\n⏱️ 7 words in 5:00"
+    );
+}
+
+#[test]
+fn only_timed_games_share_their_times() {
+    let mut game = Game::new("CRANE");
+    game.guess("CRANE").unwrap();
+    let text = share_text("Wordle Clone #7", &game, Theme::Classic, true);
+
+    // The daily and free games aren't timed, however long each guess took
+    let mut untimed = Timer::default();
+    untimed.start(0.0);
+    untimed.split(42.0);
+    assert!(untimed.splits().is_empty());
+    let shared = timed_share_text(text.clone(), "Wordle Clone #7", &untimed);
+    assert_eq!(shared, text);
+    assert!(!shared.contains('⏱'));
+
+    let mut countdown = Timer::new(Timing::Countdown, 60);
+    countdown.start(0.0);
+    countdown.split(12.5);
+    assert_eq!(
+        timed_share_text(text.clone(), "Wordle Clone", &countdown),
+        format!("{}\n⏱️ 12.5s (12.5s)", text)
+    );
+}
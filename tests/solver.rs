@@ -0,0 +1,72 @@
+use std::sync::Arc;
+use wordle_clone::engine::{Game, Rules};
+use wordle_clone::language::ENGLISH;
+use wordle_clone::solver::{candidates, entropy, Solver};
+use wordle_clone::words::WordList;
+
+const WORDS: [&str; 6] = ["CRANE", "CRATE", "GRATE", "TRACE", "SLOTH", "BLIMP"];
+
+/// A game that only knows about `WORDS`, so the solver finishes quickly.
+fn small_game(answer: &str) -> Game {
+    let text = WORDS.join("\n");
+    let words = WordList::custom(&ENGLISH, &text, Some(&text)).unwrap();
+    Game::with_words(answer, Rules::default(), Arc::new(words))
+}
+
+#[test]
+fn feedback_narrows_the_candidates() {
+    let mut game = Game::new("GRATE");
+    game.guess("CRANE").unwrap();
+    assert_eq!(candidates(&WORDS, game.history()), vec!["GRATE"]);
+
+    let mut game = Game::new("CRATE");
+    game.guess("GRATE").unwrap();
+    assert_eq!(candidates(&WORDS, game.history()), vec!["CRATE"]);
+}
+
+#[test]
+fn entropy_measures_how_evenly_a_guess_splits() {
+    assert_eq!(entropy("CRANE", &["CRANE"]), 0.0);
+    assert_eq!(entropy("CRANE", &["CRANE", "SLOTH"]), 1.0);
+    // Both answers give the same all-gray pattern
+    assert_eq!(entropy("BLIMP", &["CRANE", "TRACE"]), 0.0);
+}
+
+#[test]
+fn solver_prefers_informative_words() {
+    let game = small_game("SLOTH");
+    let mut solver = Solver::new(&game);
+    assert!(!solver.is_done());
+    solver.finish();
+    assert!(solver.is_done());
+    assert!(solver.is_for(&game));
+
+    let best = &solver.suggestions(1)[0];
+    assert_ne!(best.word, "BLIMP");
+    assert!(best.possible);
+}
+
+#[test]
+fn solver_suggests_the_answer_once_known() {
+    let mut game = small_game("GRATE");
+    game.guess("CRANE").unwrap();
+    let mut solver = Solver::new(&game);
+    solver.finish();
+    assert_eq!(solver.candidates(), ["GRATE"]);
+    assert_eq!(
+        solver.suggestions(5).iter().map(|s| s.word.as_str()).collect::<Vec<_>>(),
+        vec!["GRATE"]
+    );
+}
+
+#[test]
+fn solver_is_only_reused_for_the_same_game() {
+    let game = small_game("SLOTH");
+    let solver = Solver::new(&game);
+    assert!(solver.is_for(&game));
+
+    // Same guesses, but another word list or other rules
+    assert!(!solver.is_for(&small_game("SLOTH")));
+    let hard = Rules { hard_mode: true, ..Rules::default() };
+    assert!(!solver.is_for(&Game::with_words("SLOTH", hard, Arc::clone(game.words()))));
+}
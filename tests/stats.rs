@@ -0,0 +1,41 @@
+use std::time::Duration;
+use wordle_clone::engine::GameState;
+use wordle_clone::stats::Stats;
+
+#[test]
+fn streaks_and_distribution() {
+    let mut stats = Stats::default();
+    stats.record(&GameState::Success(3));
+    stats.record(&GameState::Success(5));
+    stats.record(&GameState::Success(3));
+    assert_eq!(stats.current_streak, 3);
+    assert_eq!(stats.distribution, vec![0, 0, 2, 0, 1]);
+
+    stats.record(&GameState::Failure);
+    stats.record(&GameState::Success(1));
+    stats.record(&GameState::Playing);
+    assert_eq!(stats.played, 5);
+    assert_eq!(stats.wins, 4);
+    assert_eq!(stats.current_streak, 1);
+    assert_eq!(stats.max_streak, 3);
+    assert_eq!(stats.win_percentage(), 80);
+    assert_eq!(stats.distribution, vec![1, 0, 2, 0, 1]);
+}
+
+#[test]
+fn no_games_played() {
+    assert_eq!(Stats::default().win_percentage(), 0);
+}
+
+#[test]
+fn best_times_are_kept() {
+    let mut stats = Stats::default();
+    stats.record_time(95.5);
+    stats.record_time(140.0);
+    assert_eq!(stats.fastest_win, Some(Duration::from_secs_f64(95.5)));
+
+    stats.record_speedrun(4);
+    stats.record_speedrun(2);
+    assert_eq!(stats.best_speedrun, 4);
+    assert_eq!(stats.played, 0);
+}
@@ -0,0 +1,75 @@
+use wordle_clone::timer::{format_time, Timer, Timing};
+
+#[test]
+fn the_clock_starts_with_the_first_letter() {
+    let mut timer = Timer::new(Timing::Countdown, 60);
+    assert_eq!(timer.elapsed(100.0), 0.0);
+    assert!(!timer.is_up(1000.0));
+
+    timer.start(10.0);
+    timer.start(20.0);
+    assert_eq!(timer.remaining(40.0), 30.0);
+    assert!(timer.is_running());
+    assert!(!timer.is_up(69.0));
+    assert!(timer.is_up(70.0));
+
+    timer.stop(50.0);
+    assert_eq!(timer.elapsed(100.0), 40.0);
+    assert!(!timer.is_up(100.0));
+}
+
+#[test]
+fn splits_time_each_guess() {
+    let mut timer = Timer::new(Timing::Speedrun, 120);
+    timer.start(5.0);
+    timer.split(17.0);
+    timer.split(20.5);
+    assert_eq!(timer.splits(), [12.0, 3.5]);
+
+    // A speedrun starts timing the next word where the last one ended
+    timer.next_word(21.0, true);
+    timer.split(30.0);
+    assert_eq!(timer.splits(), [9.0]);
+    assert_eq!(timer.solved(), 1);
+    timer.next_word(40.0, false);
+    assert_eq!(timer.solved(), 1);
+}
+
+#[test]
+fn untimed_games_never_run_out() {
+    let mut timer = Timer::default();
+    timer.start(0.0);
+    assert!(!timer.is_running());
+    assert!(!timer.is_up(1e9));
+}
+
+#[test]
+fn times_are_formatted_like_a_clock() {
+    assert_eq!(format_time(4.25), "4.2s");
+    assert_eq!(format_time(59.94), "59.9s");
+    assert_eq!(format_time(83.0), "1:23");
+    assert_eq!(format_time(600.0), "10:00");
+}
+
+#[test]
+fn a_saved_clock_picks_up_where_it_left_off() {
+    let mut timer = Timer::new(Timing::Countdown, 60);
+    timer.start(100.0);
+    timer.split(110.0);
+    let saved = timer.save(125.0);
+
+    // The next run's clock starts again from zero
+    let restored = Timer::restore(&saved, 2.0);
+    assert_eq!(restored.timing(), Timing::Countdown);
+    assert_eq!(restored.remaining(2.0), 35.0);
+    assert_eq!(restored.splits(), [10.0]);
+    assert!(restored.is_running());
+
+    let mut restored = restored;
+    restored.split(7.0);
+    assert_eq!(restored.splits(), [10.0, 20.0]);
+
+    // A clock that hadn't started yet still waits for the first letter
+    let saved = Timer::new(Timing::Speedrun, 60).save(50.0);
+    assert!(!Timer::restore(&saved, 0.0).is_started());
+}
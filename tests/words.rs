@@ -0,0 +1,55 @@
+use std::sync::Arc;
+use wordle_clone::engine::{Game, GuessError, Rules};
+use wordle_clone::language::ENGLISH;
+use wordle_clone::words::{WordList, WordListError};
+
+#[test]
+fn custom_lists_are_read_one_word_per_line() {
+    let words = WordList::custom(&ENGLISH, "# Animals\nzebra\n\n  Otter \n", None).unwrap();
+    assert_eq!(words.word_len(), 5);
+    assert_eq!(words.answers(), ["ZEBRA", "OTTER"]);
+    // Without a guess list, the built-in guesses are still accepted
+    assert!(words.is_guess("ZEBRA") && words.is_guess("CRANE"));
+
+    // Guesses must be as long as the answers
+    let errors = WordList::custom(&ENGLISH, "ZEBRA", Some("QUAGGA")).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![WordListError::WrongLength { line: 1, word: "QUAGGA".to_string(), expected: 5 }]
+    );
+}
+
+#[test]
+fn custom_guesses_replace_the_built_in_ones() {
+    let words = WordList::custom(&ENGLISH, "ZEBRA\nOTTER", Some("LLAMA\nHORSE")).unwrap();
+    assert_eq!(words.guesses(), ["HORSE", "LLAMA", "OTTER", "ZEBRA"]);
+    assert!(!words.is_guess("CRANE"));
+
+    let mut game = Game::with_words("ZEBRA", Rules::default(), Arc::new(words));
+    assert_eq!(game.guess("CRANE").unwrap_err(), GuessError::NotInWordList);
+    assert!(game.guess("LLAMA").is_ok());
+}
+
+#[test]
+fn every_problem_is_reported_with_its_line() {
+    let errors =
+        WordList::custom(&ENGLISH, "ZEBRA\nOTTER\nHIPPO\nzebra\nEMU\nGNU5", None).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            WordListError::Duplicate { line: 4, word: "ZEBRA".to_string() },
+            WordListError::WrongLength { line: 5, word: "EMU".to_string(), expected: 5 },
+            WordListError::InvalidLetter { line: 6, word: "GNU5".to_string(), letter: '5' },
+        ]
+    );
+    assert_eq!(errors[1].to_string(), "Line 5: \"EMU\" has 3 letters, expected 5");
+
+    assert_eq!(
+        WordList::custom(&ENGLISH, "# Nothing yet\n", None).unwrap_err(),
+        vec![WordListError::Empty]
+    );
+    assert_eq!(
+        WordList::custom(&ENGLISH, "OX\nYAK", None).unwrap_err(),
+        vec![WordListError::UnsupportedLength { len: 2 }]
+    );
+}